@@ -40,17 +40,23 @@ pub fn copy_binaries(config: &skia::BinariesConfiguration, artifacts: &Path) ->
     let output_directory = &config.output_directory;
 
     let target_is_windows = cargo::target().system == "windows";
-    let (skia_lib, skia_bindings_lib) = if target_is_windows {
-        ("skia.lib", "skia-bindings.lib")
-    } else {
-        ("libskia.a", "libskia-bindings.a")
+    let lib_file_name = |lib: &str| {
+        if target_is_windows {
+            format!("{}.lib", lib)
+        } else {
+            format!("lib{}.a", lib)
+        }
     };
 
-    fs::copy(output_directory.join(skia_lib), binaries.join(skia_lib))?;
-    fs::copy(
-        output_directory.join(skia_bindings_lib),
-        binaries.join(skia_bindings_lib),
-    )?;
+    for lib in config
+        .ninja_built_libraries
+        .iter()
+        .map(|lib| lib.as_str())
+        .chain(vec![skia::BINDINGS_LIB_NAME])
+    {
+        let file_name = lib_file_name(lib);
+        fs::copy(output_directory.join(&file_name), binaries.join(&file_name))?;
+    }
 
     Ok(())
 }
@@ -8,7 +8,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-const BINDINGS_LIB_NAME: &str = "skia-bindings";
+pub const BINDINGS_LIB_NAME: &str = "skia-bindings";
 const REPOSITORY_CLONE_URL: &str = "https://github.com/rust-skia/rust-skia.git";
 const REPOSITORY_DIRECTORY: &str = "rust-skia";
 
@@ -34,9 +34,12 @@ impl Default for BuildConfiguration {
             keep_inline_functions: true,
             feature_vulkan: cfg!(feature = "vulkan"),
             feature_svg: cfg!(feature = "svg"),
-            feature_animation: false,
+            feature_webp: cfg!(feature = "webp"),
+            feature_animation: cfg!(feature = "animation"),
             feature_dng: false,
-            feature_particles: false,
+            feature_particles: cfg!(feature = "particles"),
+            feature_icu: cfg!(feature = "icu"),
+            feature_shaper: cfg!(feature = "shaper"),
             all_skia_libs,
             definitions: Vec::new(),
         }
@@ -62,15 +65,24 @@ pub struct BuildConfiguration {
     /// Build with SVG support?
     feature_svg: bool,
 
-    /// Build with animation support (yet unsupported, no wrappers).
+    /// Build with WebP support?
+    feature_webp: bool,
+
+    /// Build with animation support (Skottie).
     feature_animation: bool,
 
     /// Support DNG file format (currently unsupported because of build errors).
     feature_dng: bool,
 
-    /// Build the particles module (unsupported, no wrappers).
+    /// Build the particles module.
     feature_particles: bool,
 
+    /// Build with ICU support?
+    feature_icu: bool,
+
+    /// Build the text shaper module with HarfBuzz (requires ICU).
+    feature_shaper: bool,
+
     /// As of M74, There is a bug in the Skia macOS build
     /// that requires all libraries to be built, otherwise the build would fail.
     all_skia_libs: bool,
@@ -85,9 +97,20 @@ pub struct FinalBuildConfiguration {
     /// The name value pairs passed as arguments to gn.
     pub gn_args: Vec<(String, String)>,
 
-    /// The additional definitions (cloned from the definitions of
-    /// the BuildConfiguration).
+    /// The additional definitions (the definitions of the BuildConfiguration
+    /// and the ones that indicate the enabled modules to the bindings).
     pub definitions: Definitions,
+
+    /// Skia source directories (relative to the crate's directory) whose .cpp files
+    /// are compiled into the bindings library, because they are not part of the Skia build.
+    pub binding_source_dirs: Vec<PathBuf>,
+
+    /// Skia source files (relative to the crate's directory) that are compiled into the
+    /// bindings library, because they are not part of the Skia build.
+    pub binding_sources: Vec<PathBuf>,
+
+    /// Additional include directories (relative to the crate's directory) for the bindings.
+    pub binding_include_dirs: Vec<PathBuf>,
 }
 
 impl FinalBuildConfiguration {
@@ -114,10 +137,20 @@ impl FinalBuildConfiguration {
                     if build.feature_svg { yes() } else { no() },
                 ),
                 ("skia_use_system_expat", no()),
-                ("skia_use_icu", no()),
+                ("skia_use_icu", if build.feature_icu { yes() } else { no() }),
+                ("skia_use_system_icu", no()),
+                (
+                    "skia_use_harfbuzz",
+                    if build.feature_shaper { yes() } else { no() },
+                ),
+                ("skia_use_system_harfbuzz", no()),
                 ("skia_use_system_libjpeg_turbo", no()),
                 ("skia_use_system_libpng", no()),
-                ("skia_use_libwebp", no()),
+                (
+                    "skia_use_libwebp",
+                    if build.feature_webp { yes() } else { no() },
+                ),
+                ("skia_use_system_libwebp", no()),
                 ("skia_use_system_zlib", no()),
                 (
                     "skia_enable_skottie",
@@ -192,9 +225,80 @@ impl FinalBuildConfiguration {
                 .collect()
         };
 
+        let mut definitions = build.definitions.clone();
+        let mut binding_source_dirs = Vec::new();
+        let mut binding_sources = Vec::new();
+        let mut binding_include_dirs = Vec::new();
+
+        if cargo::target().system == "linux" {
+            // The custom font managers (directory, embedded, and empty) are only built
+            // when fontconfig is not used, so we compile them along with the bindings.
+            definitions.push(("SK_ENABLE_FONTMGR_CUSTOM".into(), None));
+            for source in &[
+                "SkFontMgr_custom.cpp",
+                "SkFontMgr_custom_directory.cpp",
+                "SkFontMgr_custom_embedded.cpp",
+                "SkFontMgr_custom_empty.cpp",
+            ] {
+                binding_sources.push(Path::new("skia/src/ports").join(source));
+            }
+            binding_include_dirs.push(PathBuf::from("skia/src/ports"));
+            binding_include_dirs.push(PathBuf::from("skia/src/core"));
+            binding_include_dirs.push(PathBuf::from("skia/src/utils"));
+            // Skia uses the system's FreeType library.
+            binding_include_dirs.extend(freetype2_include_dirs());
+        }
+
+        if build.feature_svg {
+            // SkSVGDOM is not part of the Skia library, so we compile it along with the bindings.
+            binding_source_dirs.push(PathBuf::from("skia/experimental/svg/model"));
+            binding_include_dirs.push(PathBuf::from("skia/experimental/svg/model"));
+            // private headers the SVG model depends on.
+            binding_include_dirs.push(PathBuf::from("skia/src/core"));
+            binding_include_dirs.push(PathBuf::from("skia/src/utils"));
+        }
+
+        if build.feature_animation {
+            definitions.push(("SK_ENABLE_SKOTTIE".into(), None));
+            binding_include_dirs.push(PathBuf::from("skia/modules/skottie/include"));
+            // SkottieJson.h
+            binding_include_dirs.push(PathBuf::from("skia/modules/skottie/src"));
+            // SkJSON.h
+            binding_include_dirs.push(PathBuf::from("skia/src/utils"));
+        }
+
+        if build.feature_particles {
+            definitions.push(("SK_ENABLE_PARTICLES".into(), None));
+            binding_include_dirs.push(PathBuf::from("skia/modules/particles/include"));
+            // SkJSON.h
+            binding_include_dirs.push(PathBuf::from("skia/src/utils"));
+        }
+
+        if build.feature_icu {
+            definitions.push(("SK_ENABLE_ICU".into(), None));
+            // SkLoadICU() is only declared for the ICU that is built with Skia.
+            definitions.push(("SK_USING_THIRD_PARTY_ICU".into(), None));
+            binding_include_dirs.push(PathBuf::from(
+                "skia/third_party/externals/icu/source/common",
+            ));
+            // SkLoadICU.h
+            binding_include_dirs.push(PathBuf::from("skia/third_party/icu"));
+        }
+
+        if build.feature_shaper {
+            definitions.push(("SK_ENABLE_SKSHAPER".into(), None));
+            definitions.push(("SK_SHAPER_HARFBUZZ_AVAILABLE".into(), None));
+            binding_include_dirs.push(PathBuf::from("skia/modules/skshaper/include"));
+            // SkSpan.h
+            binding_include_dirs.push(PathBuf::from("skia/src/core"));
+        }
+
         FinalBuildConfiguration {
             gn_args,
-            definitions: build.definitions.clone(),
+            definitions: definitions::compress(definitions),
+            binding_source_dirs,
+            binding_sources,
+            binding_include_dirs,
         }
     }
 }
@@ -210,6 +314,9 @@ pub struct BinariesConfiguration {
 
     /// The TARGET specific link libraries we need to inform cargo about.
     pub link_libraries: Vec<String>,
+
+    /// The static libraries built by ninja we need to link with (in linking order).
+    pub ninja_built_libraries: Vec<String>,
 }
 
 impl BinariesConfiguration {
@@ -223,6 +330,33 @@ impl BinariesConfiguration {
         if build.feature_svg {
             features.push("svg")
         }
+        if build.feature_webp {
+            features.push("webp")
+        }
+        if build.feature_animation {
+            features.push("animation")
+        }
+        if build.feature_particles {
+            features.push("particles")
+        }
+        if build.feature_icu {
+            features.push("icu")
+        }
+        if build.feature_shaper {
+            features.push("shaper")
+        }
+
+        let mut ninja_built_libraries = Vec::new();
+        if build.feature_animation {
+            ninja_built_libraries.extend(vec!["skottie", "sksg"]);
+        }
+        if build.feature_animation || build.feature_shaper {
+            ninja_built_libraries.push("skshaper");
+        }
+        if build.feature_particles {
+            ninja_built_libraries.push("particles");
+        }
+        ninja_built_libraries.push("skia");
 
         let mut link_libraries = Vec::new();
 
@@ -255,6 +389,10 @@ impl BinariesConfiguration {
             features: features.iter().map(|f| f.to_string()).collect(),
             output_directory,
             link_libraries: link_libraries.iter().map(|lib| lib.to_string()).collect(),
+            ninja_built_libraries: ninja_built_libraries
+                .iter()
+                .map(|lib| lib.to_string())
+                .collect(),
         }
     }
 
@@ -266,7 +404,9 @@ impl BinariesConfiguration {
             "cargo:rustc-link-search={}",
             self.output_directory.to_str().unwrap()
         );
-        cargo::add_link_lib("static=skia");
+        for lib in &self.ninja_built_libraries {
+            cargo::add_link_lib(&format!("static={}", lib));
+        }
         cargo::add_link_lib(&format!("static={}", BINDINGS_LIB_NAME));
     }
 }
@@ -340,6 +480,34 @@ pub fn build(build: &FinalBuildConfiguration, config: &BinariesConfiguration) {
     bindgen_gen(build, &current_dir, &config.output_directory)
 }
 
+/// Returns the include directories of the system's FreeType library as reported by pkg-config,
+/// or FreeType's default location if pkg-config is not available.
+fn freetype2_include_dirs() -> Vec<PathBuf> {
+    let output = Command::new("pkg-config")
+        .args(&["--cflags-only-I", "freetype2"])
+        .stderr(Stdio::inherit())
+        .output();
+
+    match output {
+        Ok(ref output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .filter_map(|arg| {
+                if arg.starts_with("-I") {
+                    Some(PathBuf::from(&arg[2..]))
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        _ => {
+            println!(
+                "cargo:warning=pkg-config can not find freetype2, using /usr/include/freetype2"
+            );
+            vec![PathBuf::from("/usr/include/freetype2")]
+        }
+    }
+}
+
 fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_directory: &Path) {
     let mut builder = bindgen::Builder::default()
         .generate_inline_functions(true)
@@ -375,8 +543,10 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         // Types for which the binding generator pulls in stuff that can not be compiled.
         .opaque_type("SkDeferredDisplayList")
         .opaque_type("SkDeferredDisplayList_PendingPathsMap")
+        .opaque_type("SkFontData")
         // core/
         .whitelist_type("SkAutoCanvasRestore")
+        .whitelist_type("SkCodec")
         .whitelist_type("SkColorSpacePrimaries")
         .whitelist_type("SkContourMeasure")
         .whitelist_type("SkContourMeasureIter")
@@ -387,11 +557,14 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         .whitelist_type("SkDynamicMemoryWStream")
         .whitelist_type("SkFontMgr")
         .whitelist_type("SkGraphics")
+        .whitelist_type("SkJpegEncoder")
         .whitelist_type("SkMemoryStream")
         .whitelist_type("SkMultiPictureDraw")
         .whitelist_type("SkPathMeasure")
         .whitelist_type("SkPictureRecorder")
+        .whitelist_type("SkPngEncoder")
         .whitelist_type("SkVector4")
+        .whitelist_type("SkWebpEncoder")
         .whitelist_type("SkYUVASizeInfo")
         // effects/
         .whitelist_type("SkPath1DPathEffect")
@@ -428,6 +601,26 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         .whitelist_type("SkShadowUtils")
         .whitelist_type("SkShadowFlags")
         .whitelist_type("SkTextUtils")
+        // experimental/svg/model
+        .whitelist_type("SkSVGDOM")
+        // modules/skottie
+        .whitelist_type("skottie_Animation")
+        .whitelist_type("skottie_ColorPropertyHandle")
+        .whitelist_type("skottie_OpacityPropertyHandle")
+        .whitelist_type("skottie_TextPropertyHandle")
+        .opaque_type("skottie_PropertyHandle")
+        // third_party/externals/icu
+        .whitelist_type("UBiDi")
+        .whitelist_type("UBreakIterator")
+        // modules/skshaper
+        .whitelist_type("SkShaper")
+        .opaque_type("SkShaper")
+        .opaque_type("SkShaper_RunHandler")
+        // modules/particles
+        .whitelist_type("SkParticleEffect")
+        .whitelist_type("SkParticleEffectParams")
+        .opaque_type("SkParticleEffect")
+        .opaque_type("SkParticleEffectParams")
         // misc
         .whitelist_var("SK_Color.*")
         .whitelist_var("kAll_GrBackendState")
@@ -463,6 +656,43 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         cc_build.include(include_path);
     }
 
+    {
+        // SkFontDescriptor.h, SkDrawShadowInfo.h, SkStreamPriv.h
+        let include_path = current_dir.join(Path::new("skia/src/core"));
+        builder = builder.clang_arg(format!("-I{}", include_path.display()));
+        cc_build.include(include_path);
+    }
+
+    {
+        // SkFrameHolder.h
+        let include_path = current_dir.join(Path::new("skia/src/codec"));
+        builder = builder.clang_arg(format!("-I{}", include_path.display()));
+        cc_build.include(include_path);
+    }
+
+    for include_dir in &build.binding_include_dirs {
+        let include_path = current_dir.join(include_dir);
+        builder = builder.clang_arg(format!("-I{}", include_path.display()));
+        cc_build.include(include_path);
+    }
+
+    for source_dir in &build.binding_source_dirs {
+        let entries = fs::read_dir(source_dir)
+            .unwrap_or_else(|_| panic!("Unable to read {}", source_dir.display()));
+        for entry in entries {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|e| e.to_str()) == Some("cpp") {
+                cargo::add_dependent_path(path.to_str().unwrap());
+                cc_build.file(path);
+            }
+        }
+    }
+
+    for source in &build.binding_sources {
+        cargo::add_dependent_path(source.to_str().unwrap());
+        cc_build.file(source);
+    }
+
     let definitions = {
         let skia_definitions = {
             let ninja_file = output_directory.join("obj").join("skia.ninja");
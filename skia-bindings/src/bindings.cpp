@@ -1,5 +1,10 @@
 // codec/
+#include "SkAndroidCodec.h"
+#include "SkCodec.h"
+#include "SkCodecAnimation.h"
 #include "SkEncodedOrigin.h"
+// src/codec/
+#include "SkFrameHolder.h"
 // core/
 #include "SkAnnotation.h"
 #include "SkCanvas.h"
@@ -15,6 +20,9 @@
 #include "SkFlattenable.h"
 #include "SkFont.h"
 #include "SkFontArguments.h"
+// src/core/
+#include "SkDrawShadowInfo.h"
+#include "SkFontDescriptor.h"
 #include "SkFontMetrics.h"
 #include "SkFontMgr.h"
 #include "SkGraphics.h"
@@ -37,6 +45,8 @@
 #include "SkRRect.h"
 #include "SkRSXform.h"
 #include "SkStream.h"
+// src/core/
+#include "SkStreamPriv.h"
 #include "SkStrokeRec.h"
 #include "SkSurface.h"
 #include "SkSurfaceCharacterization.h"
@@ -48,6 +58,10 @@
 #include "SkYUVASizeInfo.h"
 // docs/
 #include "SkPDFDocument.h"
+// encode/
+#include "SkJpegEncoder.h"
+#include "SkPngEncoder.h"
+#include "SkWebpEncoder.h"
 // effects/
 #include "Sk1DPathEffect.h"
 #include "Sk2DPathEffect.h"
@@ -88,6 +102,7 @@
 #include "Sk3D.h"
 #include "SkCamera.h"
 #include "SkInterpolator.h"
+#include "SkNoDrawCanvas.h"
 #include "SkNullCanvas.h"
 #include "SkParsePath.h"
 #include "SkShadowUtils.h"
@@ -102,8 +117,45 @@
 
 #if defined(SK_XML)
 #include "SkSVGCanvas.h"
+// experimental/svg/model
+#include "SkSVGDOM.h"
 #endif
 
+#if defined(SK_ENABLE_FONTMGR_CUSTOM)
+#include "SkFontMgr_directory.h"
+#endif
+
+#if defined(SK_ENABLE_SKOTTIE)
+#include "Skottie.h"
+#include "SkottieProperty.h"
+// modules/skottie/src
+#include "SkottieJson.h"
+#include "SkJSON.h"
+#endif
+
+#if defined(SK_ENABLE_ICU)
+#include "SkLoadICU.h"
+#include "unicode/ubidi.h"
+#include "unicode/ubrk.h"
+#include "unicode/uloc.h"
+#endif
+
+#if defined(SK_ENABLE_SKSHAPER)
+#include "SkShaper.h"
+#endif
+
+#if defined(SK_ENABLE_PARTICLES)
+#include "SkParticleAffector.h"
+#include "SkParticleDrawable.h"
+#include "SkParticleEffect.h"
+#include "SkParticleSerialization.h"
+#include "SkJSON.h"
+#include "SkRandom.h"
+#endif
+
+#include <algorithm>
+#include <vector>
+
 template<typename T>
 inline sk_sp<T> spFromConst(const T* pt) {
     return sk_sp<T>(const_cast<T*>(pt));
@@ -117,6 +169,231 @@ extern "C" void C_SkEncodedOriginToMatrix(SkEncodedOrigin origin, int w, int h,
     *matrix = SkEncodedOriginToMatrix(origin, w, h);
 }
 
+//
+// codec/SkCodec.h
+//
+
+extern "C" SkCodec* C_SkCodec_MakeFromData(const SkData* data) {
+    return SkCodec::MakeFromData(spFromConst(data)).release();
+}
+
+// note: this function _consumes_ / deletes the stream.
+extern "C" SkCodec* C_SkCodec_MakeFromStream(SkStream* stream, SkCodec::Result* result) {
+    return SkCodec::MakeFromStream(std::unique_ptr<SkStream>(stream), result).release();
+}
+
+extern "C" void C_SkCodec_delete(SkCodec* self) {
+    delete self;
+}
+
+extern "C" void C_SkCodec_getInfo(const SkCodec* self, SkImageInfo* info) {
+    *info = self->getInfo();
+}
+
+extern "C" SkEncodedOrigin C_SkCodec_getOrigin(const SkCodec* self) {
+    return self->getOrigin();
+}
+
+extern "C" const uint8_t* C_SkCodec_getICCProfile(const SkCodec* self, size_t* size) {
+    auto profile = self->getICCProfile();
+    if (!profile || !profile->buffer) {
+        return nullptr;
+    }
+    *size = profile->size;
+    return profile->buffer;
+}
+
+extern "C" SkISize C_SkCodec_getScaledDimensions(const SkCodec* self, float desiredScale) {
+    return self->getScaledDimensions(desiredScale);
+}
+
+extern "C" bool C_SkCodec_getValidSubset(const SkCodec* self, SkIRect* desiredSubset) {
+    return self->getValidSubset(desiredSubset);
+}
+
+extern "C" SkEncodedImageFormat C_SkCodec_getEncodedFormat(const SkCodec* self) {
+    return self->getEncodedFormat();
+}
+
+extern "C" SkCodec::Result C_SkCodec_getPixels(SkCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes, const SkCodec::Options* options) {
+    return self->getPixels(*info, pixels, rowBytes, options);
+}
+
+extern "C" SkCodec::Result C_SkCodec_startIncrementalDecode(SkCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes, const SkCodec::Options* options) {
+    return self->startIncrementalDecode(*info, pixels, rowBytes, options);
+}
+
+extern "C" SkCodec::Result C_SkCodec_incrementalDecode(SkCodec* self, int* rowsDecoded) {
+    return self->incrementalDecode(rowsDecoded);
+}
+
+extern "C" SkCodec::Result C_SkCodec_startScanlineDecode(SkCodec* self, const SkImageInfo* info, const SkCodec::Options* options) {
+    return self->startScanlineDecode(*info, options);
+}
+
+extern "C" int C_SkCodec_getScanlines(SkCodec* self, void* dst, int countLines, size_t rowBytes) {
+    return self->getScanlines(dst, countLines, rowBytes);
+}
+
+extern "C" bool C_SkCodec_skipScanlines(SkCodec* self, int countLines) {
+    return self->skipScanlines(countLines);
+}
+
+extern "C" SkCodec::SkScanlineOrder C_SkCodec_getScanlineOrder(const SkCodec* self) {
+    return self->getScanlineOrder();
+}
+
+extern "C" int C_SkCodec_nextScanline(const SkCodec* self) {
+    return self->nextScanline();
+}
+
+extern "C" int C_SkCodec_outputScanline(const SkCodec* self, int inputScanline) {
+    return self->outputScanline(inputScanline);
+}
+
+extern "C" int C_SkCodec_getFrameCount(SkCodec* self) {
+    return self->getFrameCount();
+}
+
+// SkCodec::getFrameHolder() is protected.
+struct CodecFrameHolderAccess : SkCodec {
+    using SkCodec::getFrameHolder;
+};
+
+// FrameInfo does not contain the blend mode yet, so it is read from the frame holder of the
+// animated codecs. Frames of codecs without a frame holder are blended with the prior frame.
+extern "C" bool C_SkCodec_getFrameInfo(const SkCodec* self, int index, SkCodec::FrameInfo* info, SkCodecAnimation::Blend* blend) {
+    if (!self->getFrameInfo(index, info)) {
+        return false;
+    }
+    *blend = SkCodecAnimation::Blend::kPriorFrame;
+    auto holder = (self->*(&CodecFrameHolderAccess::getFrameHolder))();
+    if (holder) {
+        auto frame = holder->getFrame(index);
+        if (frame) {
+            *blend = frame->getBlend();
+        }
+    }
+    return true;
+}
+
+extern "C" int C_SkCodec_getRepetitionCount(SkCodec* self) {
+    return self->getRepetitionCount();
+}
+
+// A stream that a codec can be created from before all of its data is available.
+// Reads return only the bytes appended so far, and the stream reports its end only
+// after finish() was called.
+class IncrementalStream : public SkStream {
+public:
+    void append(const void* data, size_t size) {
+        auto bytes = static_cast<const uint8_t*>(data);
+        fBuffer.insert(fBuffer.end(), bytes, bytes + size);
+    }
+
+    void finish() {
+        fFinished = true;
+    }
+
+    size_t read(void* buffer, size_t size) override {
+        size = std::min(size, fBuffer.size() - fPosition);
+        if (buffer) {
+            memcpy(buffer, fBuffer.data() + fPosition, size);
+        }
+        fPosition += size;
+        return size;
+    }
+
+    bool isAtEnd() const override {
+        return fFinished && fPosition == fBuffer.size();
+    }
+
+    bool rewind() override {
+        fPosition = 0;
+        return true;
+    }
+
+    bool hasPosition() const override { return true; }
+    size_t getPosition() const override { return fPosition; }
+
+    bool seek(size_t position) override {
+        fPosition = std::min(position, fBuffer.size());
+        return true;
+    }
+
+    bool move(long offset) override {
+        return seek(fPosition + offset);
+    }
+
+private:
+    std::vector<uint8_t> fBuffer;
+    size_t fPosition = 0;
+    bool fFinished = false;
+};
+
+extern "C" SkStream* C_IncrementalStream_new() {
+    return new IncrementalStream();
+}
+
+extern "C" void C_IncrementalStream_append(SkStream* self, const void* data, size_t size) {
+    static_cast<IncrementalStream*>(self)->append(data, size);
+}
+
+extern "C" void C_IncrementalStream_finish(SkStream* self) {
+    static_cast<IncrementalStream*>(self)->finish();
+}
+
+//
+// codec/SkAndroidCodec.h
+//
+
+extern "C" SkAndroidCodec* C_SkAndroidCodec_MakeFromData(const SkData* data) {
+    return SkAndroidCodec::MakeFromData(spFromConst(data)).release();
+}
+
+// note: this function _consumes_ / deletes the codec.
+extern "C" SkAndroidCodec* C_SkAndroidCodec_MakeFromCodec(SkCodec* codec) {
+    return SkAndroidCodec::MakeFromCodec(std::unique_ptr<SkCodec>(codec)).release();
+}
+
+extern "C" void C_SkAndroidCodec_delete(SkAndroidCodec* self) {
+    delete self;
+}
+
+extern "C" void C_SkAndroidCodec_getInfo(const SkAndroidCodec* self, SkImageInfo* info) {
+    *info = self->getInfo();
+}
+
+extern "C" SkEncodedImageFormat C_SkAndroidCodec_getEncodedFormat(const SkAndroidCodec* self) {
+    return self->getEncodedFormat();
+}
+
+extern "C" int C_SkAndroidCodec_computeSampleSize(const SkAndroidCodec* self, SkISize* desiredSize) {
+    return self->computeSampleSize(desiredSize);
+}
+
+extern "C" SkISize C_SkAndroidCodec_getSampledDimensions(const SkAndroidCodec* self, int sampleSize) {
+    return self->getSampledDimensions(sampleSize);
+}
+
+extern "C" bool C_SkAndroidCodec_getSupportedSubset(const SkAndroidCodec* self, SkIRect* desiredSubset) {
+    return self->getSupportedSubset(desiredSubset);
+}
+
+extern "C" SkISize C_SkAndroidCodec_getSampledSubsetDimensions(const SkAndroidCodec* self, int sampleSize, const SkIRect* subset) {
+    return self->getSampledSubsetDimensions(sampleSize, *subset);
+}
+
+extern "C" SkCodec::Result C_SkAndroidCodec_getAndroidPixels(
+        SkAndroidCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes,
+        SkCodec::ZeroInitialized zeroInitialized, const SkIRect* subset, int sampleSize) {
+    SkAndroidCodec::AndroidOptions options;
+    options.fZeroInitialized = zeroInitialized;
+    options.fSubset = subset;
+    options.fSampleSize = sampleSize;
+    return self->getAndroidPixels(*info, pixels, rowBytes, &options);
+}
+
 //
 // SkSurface
 //
@@ -455,6 +732,45 @@ extern "C" SkData *C_SkEncodeBitmap(const SkBitmap *src, SkEncodedImageFormat fo
     return SkEncodeBitmap(*src, format, quality).release();
 }
 
+extern "C" bool C_SkEncodeImage(SkWStream* dst, const SkPixmap* src, SkEncodedImageFormat format, int quality) {
+    return SkEncodeImage(dst, *src, format, quality);
+}
+
+//
+// encode/SkPngEncoder.h
+//
+
+extern "C" bool C_SkPngEncoder_Encode(SkWStream* dst, const SkPixmap* src, int filterFlags, int zLibLevel, const SkDataTable* comments) {
+    SkPngEncoder::Options options;
+    options.fFilterFlags = static_cast<SkPngEncoder::FilterFlag>(filterFlags);
+    options.fZLibLevel = zLibLevel;
+    options.fComments = sk_ref_sp(comments);
+    return SkPngEncoder::Encode(dst, *src, options);
+}
+
+//
+// encode/SkJpegEncoder.h
+//
+
+extern "C" bool C_SkJpegEncoder_Encode(SkWStream* dst, const SkPixmap* src, int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    SkJpegEncoder::Options options;
+    options.fQuality = quality;
+    options.fDownsample = downsample;
+    options.fAlphaOption = alphaOption;
+    return SkJpegEncoder::Encode(dst, *src, options);
+}
+
+//
+// encode/SkWebpEncoder.h
+//
+
+extern "C" bool C_SkWebpEncoder_Encode(SkWStream* dst, const SkPixmap* src, SkWebpEncoder::Compression compression, float quality) {
+    SkWebpEncoder::Options options;
+    options.fCompression = compression;
+    options.fQuality = quality;
+    return SkWebpEncoder::Encode(dst, *src, options);
+}
+
 //
 // core/SkData.h
 //
@@ -495,6 +811,14 @@ extern "C" SkData* C_SkData_MakeEmpty() {
     return SkData::MakeEmpty().release();
 }
 
+extern "C" SkData* C_SkData_MakeFromStream(SkStream* stream, size_t size) {
+    return SkData::MakeFromStream(stream, size).release();
+}
+
+extern "C" SkData* C_SkData_MakeFromStreamToEnd(SkStream* stream) {
+    return SkCopyStreamToData(stream).release();
+}
+
 //
 // core/SkMultiPictureDraw.h
 //
@@ -870,6 +1194,10 @@ extern "C" SkPicture* C_SkPicture_MakeFromData2(const void* data, size_t size) {
     return SkPicture::MakeFromData(data, size).release();
 }
 
+extern "C" SkPicture* C_SkPicture_MakeFromStream(SkStream* stream) {
+    return SkPicture::MakeFromStream(stream).release();
+}
+
 extern "C" SkData* C_SkPicture_serialize(const SkPicture* self) {
     return self->serialize().release();
 }
@@ -1013,6 +1341,11 @@ extern "C" SkTypeface* C_SkTypeface_MakeFromFile(const char path[], int index) {
 }
 */
 
+// note: this function _consumes_ / deletes the stream.
+extern "C" SkTypeface* C_SkTypeface_MakeFromStream(SkStreamAsset* stream, int index) {
+    return SkTypeface::MakeFromStream(std::unique_ptr<SkStreamAsset>(stream), index).release();
+}
+
 extern "C" SkTypeface* C_SkTypeface_MakeFromData(const SkData* data, int index) {
     return SkTypeface::MakeFromData(sk_sp<SkData>(const_cast<SkData*>(data)), index).release();
 }
@@ -1029,6 +1362,50 @@ extern "C" SkTypeface* C_SkTypeface_MakeDeserialize(SkStream* stream) {
     return SkTypeface::MakeDeserialize(stream).release();
 }
 
+// Returns the data of the font file, or nullptr if it is not available.
+extern "C" SkData* C_SkTypeface_openStream(const SkTypeface* self, int* ttcIndex) {
+    auto stream = self->openStream(ttcIndex);
+    if (!stream) {
+        return nullptr;
+    }
+    auto length = stream->getLength();
+    return SkData::MakeFromStream(stream.get(), length).release();
+}
+
+extern "C" SkFontData* C_SkTypeface_makeFontData(const SkTypeface* self) {
+    return self->makeFontData().release();
+}
+
+//
+// src/core/SkFontDescriptor.h
+//
+
+extern "C" void C_SkFontData_delete(SkFontData* self) {
+    delete self;
+}
+
+// Returns the data of the font file, or nullptr if the font data has no stream.
+extern "C" SkData* C_SkFontData_getData(SkFontData* self) {
+    auto stream = self->getStream();
+    if (!stream) {
+        return nullptr;
+    }
+    stream->rewind();
+    return SkData::MakeFromStream(stream, stream->getLength()).release();
+}
+
+extern "C" int C_SkFontData_getIndex(const SkFontData* self) {
+    return self->getIndex();
+}
+
+extern "C" int C_SkFontData_getAxisCount(const SkFontData* self) {
+    return self->getAxisCount();
+}
+
+extern "C" const SkFixed* C_SkFontData_getAxis(const SkFontData* self) {
+    return self->getAxis();
+}
+
 extern "C" void C_SkTypeface_LocalizedStrings_unref(SkTypeface::LocalizedStrings* self) {
     self->unref();
 }
@@ -1083,6 +1460,10 @@ extern "C" void C_SkFont_ConstructFromTypefaceWithSizeScaleAndSkew(SkFont* unini
     new(uninitialized) SkFont(spFromConst(typeface), size, scaleX, skewX);
 }
 
+extern "C" void C_SkFont_CopyConstruct(SkFont* uninitialized, const SkFont* font) {
+    new(uninitialized) SkFont(*font);
+}
+
 extern "C" bool C_SkFont_Equals(const SkFont* self, const SkFont* other) {
     return *self == *other;
 }
@@ -1153,10 +1534,210 @@ extern "C" SkTypeface* C_SkFontMgr_makeFromStream(const SkFontMgr* self, SkStrea
     return self->makeFromStream(std::unique_ptr<SkStreamAsset>(stream), ttcIndex).release();
 }
 
+extern "C" SkTypeface* C_SkFontMgr_makeFromData(const SkFontMgr* self, const SkData* data, int ttcIndex) {
+    return self->makeFromData(spFromConst(data), ttcIndex).release();
+}
+
+extern "C" SkTypeface* C_SkFontMgr_legacyMakeTypeface(const SkFontMgr* self, const char* familyName, SkFontStyle style) {
+    return self->legacyMakeTypeface(familyName, style).release();
+}
+
 extern "C" SkFontMgr* C_SkFontMgr_RefDefault() {
     return SkFontMgr::RefDefault().release();
 }
 
+// A style set of the typefaces of one family.
+class TypefaceStyleSet : public SkFontStyleSet {
+public:
+    explicit TypefaceStyleSet(std::vector<sk_sp<SkTypeface>> typefaces) : fTypefaces(std::move(typefaces)) {}
+
+    int count() override { return static_cast<int>(fTypefaces.size()); }
+
+    void getStyle(int index, SkFontStyle* style, SkString* name) override {
+        if (style) {
+            *style = fTypefaces[index]->fontStyle();
+        }
+        if (name) {
+            name->reset();
+        }
+    }
+
+    SkTypeface* createTypeface(int index) override {
+        return SkRef(fTypefaces[index].get());
+    }
+
+    SkTypeface* matchStyle(const SkFontStyle& pattern) override {
+        return this->matchStyleCSS3(pattern);
+    }
+
+private:
+    const std::vector<sk_sp<SkTypeface>> fTypefaces;
+};
+
+typedef int (*RustFontMgrCountFamilies)(void* provider);
+typedef void (*RustFontMgrGetFamilyName)(void* provider, int index, SkString* familyName);
+typedef SkTypeface* (*RustFontMgrMatchFamilyStyle)(void* provider, const char* familyName, const SkFontStyle* style);
+typedef SkTypeface* (*RustFontMgrMatchFamilyStyleCharacter)(
+        void* provider, const char* familyName, const SkFontStyle* style,
+        const char* bcp47[], int bcp47Count, SkUnichar character);
+typedef SkTypeface* (*RustFontMgrMakeFromData)(void* provider, const uint8_t* data, size_t length, int ttcIndex);
+typedef void (*RustFontMgrDrop)(void* provider);
+
+class RustFontMgr : public SkFontMgr {
+public:
+    RustFontMgr(void* provider,
+                RustFontMgrCountFamilies countFamilies,
+                RustFontMgrGetFamilyName getFamilyName,
+                RustFontMgrMatchFamilyStyle matchFamilyStyle,
+                RustFontMgrMatchFamilyStyleCharacter matchFamilyStyleCharacter,
+                RustFontMgrMakeFromData makeFromData,
+                RustFontMgrDrop drop)
+        : fProvider(provider),
+          fCountFamilies(countFamilies),
+          fGetFamilyName(getFamilyName),
+          fMatchFamilyStyle(matchFamilyStyle),
+          fMatchFamilyStyleCharacter(matchFamilyStyleCharacter),
+          fMakeFromData(makeFromData),
+          fDrop(drop) {}
+
+    ~RustFontMgr() override {
+        fDrop(fProvider);
+    }
+
+protected:
+    int onCountFamilies() const override {
+        return fCountFamilies(fProvider);
+    }
+
+    void onGetFamilyName(int index, SkString* familyName) const override {
+        fGetFamilyName(fProvider, index, familyName);
+    }
+
+    SkFontStyleSet* onCreateStyleSet(int index) const override {
+        SkString familyName;
+        this->onGetFamilyName(index, &familyName);
+        return this->onMatchFamily(familyName.c_str());
+    }
+
+    // The provider only matches styles, so the style set of a family contains the
+    // typefaces that match the normal, bold, italic, and bold italic styles.
+    SkFontStyleSet* onMatchFamily(const char familyName[]) const override {
+        std::vector<sk_sp<SkTypeface>> typefaces;
+        for (auto style : { SkFontStyle::Normal(), SkFontStyle::Bold(), SkFontStyle::Italic(), SkFontStyle::BoldItalic() }) {
+            sk_sp<SkTypeface> typeface(this->onMatchFamilyStyle(familyName, style));
+            if (typeface && std::none_of(typefaces.begin(), typefaces.end(), [&](const sk_sp<SkTypeface>& t) {
+                    return t->uniqueID() == typeface->uniqueID();
+                })) {
+                typefaces.push_back(std::move(typeface));
+            }
+        }
+        return new TypefaceStyleSet(std::move(typefaces));
+    }
+
+    SkTypeface* onMatchFamilyStyle(const char familyName[], const SkFontStyle& style) const override {
+        return fMatchFamilyStyle(fProvider, familyName ? familyName : "", &style);
+    }
+
+    SkTypeface* onMatchFamilyStyleCharacter(const char familyName[], const SkFontStyle& style,
+                                            const char* bcp47[], int bcp47Count,
+                                            SkUnichar character) const override {
+        return fMatchFamilyStyleCharacter(fProvider, familyName ? familyName : "", &style, bcp47, bcp47Count, character);
+    }
+
+    SkTypeface* onMatchFaceStyle(const SkTypeface* typeface, const SkFontStyle& style) const override {
+        SkString familyName;
+        typeface->getFamilyName(&familyName);
+        return this->onMatchFamilyStyle(familyName.c_str(), style);
+    }
+
+    sk_sp<SkTypeface> onMakeFromData(sk_sp<SkData> data, int ttcIndex) const override {
+        if (!data) {
+            return nullptr;
+        }
+        return sk_sp<SkTypeface>(fMakeFromData(fProvider, data->bytes(), data->size(), ttcIndex));
+    }
+
+    sk_sp<SkTypeface> onMakeFromStreamIndex(std::unique_ptr<SkStreamAsset> stream, int ttcIndex) const override {
+        if (!stream) {
+            return nullptr;
+        }
+        auto length = stream->getLength();
+        return this->onMakeFromData(SkData::MakeFromStream(stream.get(), length), ttcIndex);
+    }
+
+    sk_sp<SkTypeface> onMakeFromFile(const char path[], int ttcIndex) const override {
+        return this->onMakeFromData(SkData::MakeFromFileName(path), ttcIndex);
+    }
+
+    // Without a family name, the first family of the provider is used.
+    sk_sp<SkTypeface> onLegacyMakeTypeface(const char familyName[], SkFontStyle style) const override {
+        if (familyName) {
+            if (auto typeface = sk_sp<SkTypeface>(this->onMatchFamilyStyle(familyName, style))) {
+                return typeface;
+            }
+        }
+        if (this->onCountFamilies() == 0) {
+            return nullptr;
+        }
+        SkString defaultFamilyName;
+        this->onGetFamilyName(0, &defaultFamilyName);
+        return sk_sp<SkTypeface>(this->onMatchFamilyStyle(defaultFamilyName.c_str(), style));
+    }
+
+private:
+    void* fProvider;
+    RustFontMgrCountFamilies fCountFamilies;
+    RustFontMgrGetFamilyName fGetFamilyName;
+    RustFontMgrMatchFamilyStyle fMatchFamilyStyle;
+    RustFontMgrMatchFamilyStyleCharacter fMatchFamilyStyleCharacter;
+    RustFontMgrMakeFromData fMakeFromData;
+    RustFontMgrDrop fDrop;
+};
+
+// provider is owned by the font manager and dropped when the font manager is destroyed.
+extern "C" SkFontMgr* C_RustFontMgr_new(
+        void* provider,
+        RustFontMgrCountFamilies countFamilies,
+        RustFontMgrGetFamilyName getFamilyName,
+        RustFontMgrMatchFamilyStyle matchFamilyStyle,
+        RustFontMgrMatchFamilyStyleCharacter matchFamilyStyleCharacter,
+        RustFontMgrMakeFromData makeFromData,
+        RustFontMgrDrop drop) {
+    return new RustFontMgr(provider, countFamilies, getFamilyName, matchFamilyStyle, matchFamilyStyleCharacter, makeFromData, drop);
+}
+
+#if defined(SK_ENABLE_FONTMGR_CUSTOM)
+
+//
+// ports/SkFontMgr_directory.h, src/ports/SkFontMgr_custom_embedded.cpp, src/ports/SkFontMgr_custom_empty.cpp
+//
+
+struct SkEmbeddedResource { const uint8_t* data; size_t size; };
+struct SkEmbeddedResourceHeader { const SkEmbeddedResource* entries; int count; };
+
+SK_API sk_sp<SkFontMgr> SkFontMgr_New_Custom_Embedded(const SkEmbeddedResourceHeader* header);
+SK_API sk_sp<SkFontMgr> SkFontMgr_New_Custom_Empty();
+
+extern "C" SkFontMgr* C_SkFontMgr_New_Custom_Directory(const char* dir) {
+    return SkFontMgr_New_Custom_Directory(dir).release();
+}
+
+// The font data is not copied and must outlive the font manager and all the typefaces created from it.
+extern "C" SkFontMgr* C_SkFontMgr_New_Custom_Embedded(const uint8_t* const* datas, const size_t* sizes, int count) {
+    std::vector<SkEmbeddedResource> entries;
+    for (int i = 0; i < count; ++i) {
+        entries.push_back(SkEmbeddedResource { datas[i], sizes[i] });
+    }
+    SkEmbeddedResourceHeader header { entries.data(), count };
+    return SkFontMgr_New_Custom_Embedded(&header).release();
+}
+
+extern "C" SkFontMgr* C_SkFontMgr_New_Custom_Empty() {
+    return SkFontMgr_New_Custom_Empty().release();
+}
+
+#endif
+
 //
 // core/SkFontParameters.h
 //
@@ -1378,6 +1959,69 @@ extern "C" void C_SkDrawable_GpuDrawHandler_draw(SkDrawable::GpuDrawHandler *sel
     self->draw(*info);
 }
 
+typedef void (*RustDrawableDraw)(void* drawable, SkCanvas* canvas);
+typedef void (*RustDrawableGetBounds)(void* drawable, SkRect* bounds);
+// Returns nullptr if the drawable does not provide its own snapshot.
+typedef SkPicture* (*RustDrawableNewPictureSnapshot)(void* drawable);
+typedef void (*RustDrawableDrop)(void* drawable);
+
+class RustDrawable : public SkDrawable {
+public:
+    RustDrawable(void* drawable,
+                 RustDrawableDraw draw,
+                 RustDrawableGetBounds getBounds,
+                 RustDrawableNewPictureSnapshot newPictureSnapshot,
+                 RustDrawableDrop drop)
+        : fDrawable(drawable),
+          fDraw(draw),
+          fGetBounds(getBounds),
+          fNewPictureSnapshot(newPictureSnapshot),
+          fDrop(drop) {}
+
+    ~RustDrawable() override {
+        fDrop(fDrawable);
+    }
+
+protected:
+    SkRect onGetBounds() override {
+        SkRect bounds = SkRect::MakeEmpty();
+        fGetBounds(fDrawable, &bounds);
+        return bounds;
+    }
+
+    void onDraw(SkCanvas* canvas) override {
+        fDraw(fDrawable, canvas);
+    }
+
+    SkPicture* onNewPictureSnapshot() override {
+        if (auto picture = fNewPictureSnapshot(fDrawable)) {
+            return picture;
+        }
+        return SkDrawable::onNewPictureSnapshot();
+    }
+
+private:
+    void* fDrawable;
+    RustDrawableDraw fDraw;
+    RustDrawableGetBounds fGetBounds;
+    RustDrawableNewPictureSnapshot fNewPictureSnapshot;
+    RustDrawableDrop fDrop;
+};
+
+// drawable is owned by the SkDrawable and dropped when the SkDrawable is destroyed.
+extern "C" SkDrawable* C_RustDrawable_new(
+        void* drawable,
+        RustDrawableDraw draw,
+        RustDrawableGetBounds getBounds,
+        RustDrawableNewPictureSnapshot newPictureSnapshot,
+        RustDrawableDrop drop) {
+    return new RustDrawable(drawable, draw, getBounds, newPictureSnapshot, drop);
+}
+
+extern "C" SkPicture* C_SkDrawable_newPictureSnapshot(SkDrawable* self) {
+    return self->newPictureSnapshot();
+}
+
 //
 // SkImageFilter
 //
@@ -1595,6 +2239,99 @@ extern "C" bool C_SkWStream_write(SkWStream* self, const void* buffer, size_t si
     return self->write(buffer, size);
 }
 
+//
+// RustStream : public SkStreamAsset
+//
+
+typedef size_t (*RustStreamRead)(void* reader, void* buffer, size_t size);
+typedef bool (*RustStreamSeek)(void* reader, size_t position);
+typedef void (*RustStreamDrop)(void* reader);
+
+// A stream that reads from a Rust Read implementation, and if seek is set, from a Read + Seek
+// implementation with a known length. The stream owns the reader and drops it when it is deleted.
+class RustStream : public SkStreamAsset {
+public:
+    RustStream(void* reader, size_t length, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop)
+        : fReader(reader), fLength(length), fRead(read), fSeek(seek), fDrop(drop), fPosition(0), fAtEnd(false) {}
+
+    ~RustStream() override {
+        fDrop(fReader);
+    }
+
+    size_t read(void* buffer, size_t size) override {
+        if (!buffer && fSeek) {
+            // the reader may have delivered more bytes than the length measured up front.
+            size = std::min(size, fPosition < fLength ? fLength - fPosition : 0);
+            return this->move(size) ? size : 0;
+        }
+
+        uint8_t skipBuffer[4096];
+        size_t bytesRead = 0;
+        while (bytesRead < size && !fAtEnd) {
+            size_t toRead = size - bytesRead;
+            void* dst = buffer ? static_cast<uint8_t*>(buffer) + bytesRead : skipBuffer;
+            if (!buffer) {
+                toRead = std::min(toRead, sizeof(skipBuffer));
+            }
+            size_t justRead = fRead(fReader, dst, toRead);
+            if (justRead == 0) {
+                fAtEnd = true;
+            }
+            bytesRead += justRead;
+        }
+        fPosition += bytesRead;
+        return bytesRead;
+    }
+
+    bool isAtEnd() const override {
+        return fAtEnd || (fSeek && fPosition == fLength);
+    }
+
+    bool rewind() override {
+        return this->seek(0);
+    }
+
+    bool hasPosition() const override { return true; }
+    size_t getPosition() const override { return fPosition; }
+
+    bool seek(size_t position) override {
+        if (!fSeek) {
+            return false;
+        }
+        position = std::min(position, fLength);
+        if (!fSeek(fReader, position)) {
+            return false;
+        }
+        fPosition = position;
+        fAtEnd = fPosition == fLength;
+        return true;
+    }
+
+    bool move(long offset) override {
+        return this->seek(offset < 0 && size_t(-offset) > fPosition ? 0 : fPosition + offset);
+    }
+
+    bool hasLength() const override { return fSeek != nullptr; }
+    size_t getLength() const override { return fLength; }
+
+private:
+    // Rust readers can not be duplicated.
+    SkStreamAsset* onDuplicate() const override { return nullptr; }
+    SkStreamAsset* onFork() const override { return nullptr; }
+
+    void* fReader;
+    size_t fLength;
+    RustStreamRead fRead;
+    RustStreamSeek fSeek;
+    RustStreamDrop fDrop;
+    size_t fPosition;
+    bool fAtEnd;
+};
+
+extern "C" SkStreamAsset* C_RustStream_new(void* reader, size_t length, RustStreamRead read, RustStreamSeek seek, RustStreamDrop drop) {
+    return new RustStream(reader, length, read, seek, drop);
+}
+
 //
 // SkMemoryStream: public SkStreamMemory
 //
@@ -1620,16 +2357,57 @@ extern "C" SkStreamAsset* C_SkDynamicMemoryWStream_detachAsStream(SkDynamicMemor
 }
 
 //
-// SkGradientShader
+// RustWStream : public SkWStream
 //
 
-extern "C" SkShader* C_SkGradientShader_MakeLinear(const SkPoint pts[2], const SkColor colors[], const SkScalar pos[], int count, SkTileMode mode, uint32_t flags, const SkMatrix* localMatrix) {
-    return SkGradientShader::MakeLinear(pts, colors, pos, count, mode, flags, localMatrix).release();
-}
+typedef bool (*RustWStreamWrite)(void* writer, const void* buffer, size_t size);
+typedef void (*RustWStreamFlush)(void* writer);
 
-extern "C" SkShader* C_SkGradientShader_MakeLinear2(const SkPoint pts[2], const SkColor4f colors[], const SkColorSpace* colorSpace, const SkScalar pos[], int count, SkTileMode mode, uint32_t flags, const SkMatrix* localMatrix) {
-    return SkGradientShader::MakeLinear(pts, colors, spFromConst(colorSpace), pos, count, mode, flags, localMatrix).release();
-}
+// A write stream that forwards all writes and flushes to a Rust Write implementation.
+class RustWStream : public SkWStream {
+public:
+    RustWStream(void* writer, RustWStreamWrite write, RustWStreamFlush flush)
+        : fWriter(writer), fWrite(write), fFlush(flush), fBytesWritten(0) {}
+
+    bool write(const void* buffer, size_t size) override {
+        fBytesWritten += size;
+        return fWrite(fWriter, buffer, size);
+    }
+
+    void flush() override {
+        fFlush(fWriter);
+    }
+
+    size_t bytesWritten() const override {
+        return fBytesWritten;
+    }
+
+private:
+    void* fWriter;
+    RustWStreamWrite fWrite;
+    RustWStreamFlush fFlush;
+    size_t fBytesWritten;
+};
+
+extern "C" SkWStream* C_RustWStream_new(void* writer, RustWStreamWrite write, RustWStreamFlush flush) {
+    return new RustWStream(writer, write, flush);
+}
+
+extern "C" void C_RustWStream_delete(SkWStream* self) {
+    delete self;
+}
+
+//
+// SkGradientShader
+//
+
+extern "C" SkShader* C_SkGradientShader_MakeLinear(const SkPoint pts[2], const SkColor colors[], const SkScalar pos[], int count, SkTileMode mode, uint32_t flags, const SkMatrix* localMatrix) {
+    return SkGradientShader::MakeLinear(pts, colors, pos, count, mode, flags, localMatrix).release();
+}
+
+extern "C" SkShader* C_SkGradientShader_MakeLinear2(const SkPoint pts[2], const SkColor4f colors[], const SkColorSpace* colorSpace, const SkScalar pos[], int count, SkTileMode mode, uint32_t flags, const SkMatrix* localMatrix) {
+    return SkGradientShader::MakeLinear(pts, colors, spFromConst(colorSpace), pos, count, mode, flags, localMatrix).release();
+}
 
 extern "C" SkShader* C_SkGradientShader_MakeRadial(const SkPoint* center, SkScalar radius, const SkColor colors[], const SkScalar pos[], int count, SkTileMode mode, uint32_t flags, const SkMatrix* localMatrix) {
     return SkGradientShader::MakeRadial(*center, radius, colors, pos, count, mode, flags, localMatrix).release();
@@ -2023,6 +2801,10 @@ extern "C" SkDocument* C_SkPDF_MakeDocument(SkWStream* stream, const SkPDF::Meta
     return SkPDF::MakeDocument(stream, *metadata).release();
 }
 
+extern "C" void C_SkPDF_SetNodeId(SkCanvas* canvas, int nodeID) {
+    SkPDF::SetNodeId(canvas, nodeID);
+}
+
 //
 // GrBackendFormat
 //
@@ -2158,6 +2940,258 @@ extern "C" SkCanvas* C_SkMakeNullCanvas() {
     return SkMakeNullCanvas().release();
 }
 
+//
+// utils/SkNoDrawCanvas.h
+//
+
+// The functions a canvas implemented in Rust forwards its calls to.
+// Nullable pointers are marked with OrNull.
+struct RustCanvasBackend {
+    void* backend;
+
+    void (*willSave)(void* backend);
+    void (*willSaveLayer)(void* backend, const SkRect* boundsOrNull, const SkPaint* paintOrNull);
+    void (*didRestore)(void* backend);
+    void (*didConcat)(void* backend, const SkMatrix* matrix);
+    void (*didSetMatrix)(void* backend, const SkMatrix* matrix);
+
+    void (*clipRect)(void* backend, const SkRect* rect, int op, bool antiAlias);
+    void (*clipRRect)(void* backend, const SkRRect* rrect, int op, bool antiAlias);
+    void (*clipPath)(void* backend, const SkPath* path, int op, bool antiAlias);
+    void (*clipRegion)(void* backend, const SkRegion* deviceRegion, int op);
+
+    void (*drawPaint)(void* backend, const SkPaint* paint);
+    void (*drawPoints)(void* backend, SkCanvas::PointMode mode, size_t count, const SkPoint* points, const SkPaint* paint);
+    void (*drawRect)(void* backend, const SkRect* rect, const SkPaint* paint);
+    void (*drawRegion)(void* backend, const SkRegion* region, const SkPaint* paint);
+    void (*drawOval)(void* backend, const SkRect* oval, const SkPaint* paint);
+    void (*drawArc)(void* backend, const SkRect* oval, SkScalar startAngle, SkScalar sweepAngle, bool useCenter, const SkPaint* paint);
+    void (*drawRRect)(void* backend, const SkRRect* rrect, const SkPaint* paint);
+    void (*drawDRRect)(void* backend, const SkRRect* outer, const SkRRect* inner, const SkPaint* paint);
+    void (*drawPath)(void* backend, const SkPath* path, const SkPaint* paint);
+    void (*drawTextBlob)(void* backend, const SkTextBlob* blob, SkScalar x, SkScalar y, const SkPaint* paint);
+    void (*drawImage)(void* backend, const SkImage* image, SkScalar left, SkScalar top, const SkPaint* paintOrNull);
+    void (*drawImageRect)(void* backend, const SkImage* image, const SkRect* srcOrNull, const SkRect* dst, const SkPaint* paintOrNull, SkCanvas::SrcRectConstraint constraint);
+    void (*drawVertices)(void* backend, const SkVertices* vertices, SkBlendMode mode, const SkPaint* paint);
+    void (*drawAnnotation)(void* backend, const SkRect* rect, const char* key, const SkData* valueOrNull);
+    void (*drawPatch)(void* backend, const SkPoint* cubics, const SkColor* colorsOrNull, const SkPoint* texCoordsOrNull, SkBlendMode mode, const SkPaint* paint);
+    void (*drawImageNine)(void* backend, const SkImage* image, const SkIRect* center, const SkRect* dst, const SkPaint* paintOrNull);
+    void (*drawImageLattice)(void* backend, const SkImage* image, const SkCanvas::Lattice* lattice, const SkRect* dst, const SkPaint* paintOrNull);
+    void (*drawAtlas)(void* backend, const SkImage* atlas, const SkRSXform* xforms, const SkRect* texs, const SkColor* colorsOrNull, int count,
+                      SkBlendMode mode, const SkRect* cullOrNull, const SkPaint* paintOrNull);
+    void (*drawShadowRec)(void* backend, const SkPath* path, const SkPoint3* zPlaneParams, const SkPoint3* lightPos, SkScalar lightRadius,
+                          SkColor ambientColor, SkColor spotColor, uint32_t flags);
+    void (*drawEdgeAAQuad)(void* backend, const SkRect* rect, const SkPoint* clipOrNull, unsigned aaFlags, SkColor color, SkBlendMode mode);
+    void (*drawEdgeAAImageSet)(void* backend, const SkCanvas::ImageSetEntry* imageSet, int count, const SkPoint* dstClips, int dstClipCount,
+                               const SkMatrix* preViewMatrices, int preViewMatrixCount, const SkPaint* paintOrNull,
+                               SkCanvas::SrcRectConstraint constraint);
+};
+
+// A canvas that does not draw, but forwards its calls to a backend implemented in Rust.
+// Bitmaps are forwarded as images, pictures and drawables are played back into the canvas.
+// Clip ops are passed as their underlying values, because the deprecated ones are not known to Rust.
+class RustCanvas : public SkNoDrawCanvas {
+public:
+    RustCanvas(int width, int height, const RustCanvasBackend& backend)
+        : SkNoDrawCanvas(width, height), fBackend(backend) {}
+
+protected:
+    void willSave() override {
+        fBackend.willSave(fBackend.backend);
+    }
+
+    SaveLayerStrategy getSaveLayerStrategy(const SaveLayerRec& rec) override {
+        fBackend.willSaveLayer(fBackend.backend, rec.fBounds, rec.fPaint);
+        return kNoLayer_SaveLayerStrategy;
+    }
+
+    void didRestore() override {
+        fBackend.didRestore(fBackend.backend);
+    }
+
+    void didConcat(const SkMatrix& matrix) override {
+        fBackend.didConcat(fBackend.backend, &matrix);
+    }
+
+    void didSetMatrix(const SkMatrix& matrix) override {
+        fBackend.didSetMatrix(fBackend.backend, &matrix);
+    }
+
+    void onClipRect(const SkRect& rect, SkClipOp op, ClipEdgeStyle edgeStyle) override {
+        fBackend.clipRect(fBackend.backend, &rect, static_cast<int>(op), edgeStyle == kSoft_ClipEdgeStyle);
+        SkNoDrawCanvas::onClipRect(rect, op, edgeStyle);
+    }
+
+    void onClipRRect(const SkRRect& rrect, SkClipOp op, ClipEdgeStyle edgeStyle) override {
+        fBackend.clipRRect(fBackend.backend, &rrect, static_cast<int>(op), edgeStyle == kSoft_ClipEdgeStyle);
+        SkNoDrawCanvas::onClipRRect(rrect, op, edgeStyle);
+    }
+
+    void onClipPath(const SkPath& path, SkClipOp op, ClipEdgeStyle edgeStyle) override {
+        fBackend.clipPath(fBackend.backend, &path, static_cast<int>(op), edgeStyle == kSoft_ClipEdgeStyle);
+        SkNoDrawCanvas::onClipPath(path, op, edgeStyle);
+    }
+
+    void onClipRegion(const SkRegion& deviceRegion, SkClipOp op) override {
+        fBackend.clipRegion(fBackend.backend, &deviceRegion, static_cast<int>(op));
+        SkNoDrawCanvas::onClipRegion(deviceRegion, op);
+    }
+
+    void onDrawPaint(const SkPaint& paint) override {
+        fBackend.drawPaint(fBackend.backend, &paint);
+    }
+
+    void onDrawPoints(PointMode mode, size_t count, const SkPoint points[], const SkPaint& paint) override {
+        fBackend.drawPoints(fBackend.backend, mode, count, points, &paint);
+    }
+
+    void onDrawRect(const SkRect& rect, const SkPaint& paint) override {
+        fBackend.drawRect(fBackend.backend, &rect, &paint);
+    }
+
+    void onDrawRegion(const SkRegion& region, const SkPaint& paint) override {
+        fBackend.drawRegion(fBackend.backend, &region, &paint);
+    }
+
+    void onDrawOval(const SkRect& oval, const SkPaint& paint) override {
+        fBackend.drawOval(fBackend.backend, &oval, &paint);
+    }
+
+    void onDrawArc(const SkRect& oval, SkScalar startAngle, SkScalar sweepAngle, bool useCenter, const SkPaint& paint) override {
+        fBackend.drawArc(fBackend.backend, &oval, startAngle, sweepAngle, useCenter, &paint);
+    }
+
+    void onDrawRRect(const SkRRect& rrect, const SkPaint& paint) override {
+        fBackend.drawRRect(fBackend.backend, &rrect, &paint);
+    }
+
+    void onDrawDRRect(const SkRRect& outer, const SkRRect& inner, const SkPaint& paint) override {
+        fBackend.drawDRRect(fBackend.backend, &outer, &inner, &paint);
+    }
+
+    void onDrawPath(const SkPath& path, const SkPaint& paint) override {
+        fBackend.drawPath(fBackend.backend, &path, &paint);
+    }
+
+    void onDrawTextBlob(const SkTextBlob* blob, SkScalar x, SkScalar y, const SkPaint& paint) override {
+        fBackend.drawTextBlob(fBackend.backend, blob, x, y, &paint);
+    }
+
+    void onDrawImage(const SkImage* image, SkScalar left, SkScalar top, const SkPaint* paint) override {
+        fBackend.drawImage(fBackend.backend, image, left, top, paint);
+    }
+
+    void onDrawImageRect(const SkImage* image, const SkRect* src, const SkRect& dst, const SkPaint* paint,
+                         SrcRectConstraint constraint) override {
+        fBackend.drawImageRect(fBackend.backend, image, src, &dst, paint, constraint);
+    }
+
+    void onDrawBitmap(const SkBitmap& bitmap, SkScalar left, SkScalar top, const SkPaint* paint) override {
+        if (auto image = SkImage::MakeFromBitmap(bitmap)) {
+            this->onDrawImage(image.get(), left, top, paint);
+        }
+    }
+
+    void onDrawBitmapRect(const SkBitmap& bitmap, const SkRect* src, const SkRect& dst, const SkPaint* paint,
+                          SrcRectConstraint constraint) override {
+        if (auto image = SkImage::MakeFromBitmap(bitmap)) {
+            this->onDrawImageRect(image.get(), src, dst, paint, constraint);
+        }
+    }
+
+    void onDrawVerticesObject(const SkVertices* vertices, const SkVertices::Bone[], int, SkBlendMode mode,
+                              const SkPaint& paint) override {
+        fBackend.drawVertices(fBackend.backend, vertices, mode, &paint);
+    }
+
+    void onDrawAnnotation(const SkRect& rect, const char key[], SkData* value) override {
+        fBackend.drawAnnotation(fBackend.backend, &rect, key, value);
+    }
+
+    void onDrawPatch(const SkPoint cubics[12], const SkColor colors[4], const SkPoint texCoords[4], SkBlendMode mode,
+                     const SkPaint& paint) override {
+        fBackend.drawPatch(fBackend.backend, cubics, colors, texCoords, mode, &paint);
+    }
+
+    void onDrawImageNine(const SkImage* image, const SkIRect& center, const SkRect& dst, const SkPaint* paint) override {
+        fBackend.drawImageNine(fBackend.backend, image, &center, &dst, paint);
+    }
+
+    void onDrawImageLattice(const SkImage* image, const Lattice& lattice, const SkRect& dst, const SkPaint* paint) override {
+        fBackend.drawImageLattice(fBackend.backend, image, &lattice, &dst, paint);
+    }
+
+    void onDrawBitmapNine(const SkBitmap& bitmap, const SkIRect& center, const SkRect& dst, const SkPaint* paint) override {
+        if (auto image = SkImage::MakeFromBitmap(bitmap)) {
+            this->onDrawImageNine(image.get(), center, dst, paint);
+        }
+    }
+
+    void onDrawBitmapLattice(const SkBitmap& bitmap, const Lattice& lattice, const SkRect& dst, const SkPaint* paint) override {
+        if (auto image = SkImage::MakeFromBitmap(bitmap)) {
+            this->onDrawImageLattice(image.get(), lattice, dst, paint);
+        }
+    }
+
+    void onDrawAtlas(const SkImage* atlas, const SkRSXform xforms[], const SkRect texs[], const SkColor colors[], int count,
+                     SkBlendMode mode, const SkRect* cull, const SkPaint* paint) override {
+        fBackend.drawAtlas(fBackend.backend, atlas, xforms, texs, colors, count, mode, cull, paint);
+    }
+
+    void onDrawShadowRec(const SkPath& path, const SkDrawShadowRec& rec) override {
+        fBackend.drawShadowRec(fBackend.backend, &path, &rec.fZPlaneParams, &rec.fLightPos, rec.fLightRadius,
+                               rec.fAmbientColor, rec.fSpotColor, rec.fFlags);
+    }
+
+    void onDrawEdgeAAQuad(const SkRect& rect, const SkPoint clip[4], QuadAAFlags aaFlags, SkColor color,
+                          SkBlendMode mode) override {
+        fBackend.drawEdgeAAQuad(fBackend.backend, &rect, clip, aaFlags, color, mode);
+    }
+
+    void onDrawEdgeAAImageSet(const ImageSetEntry imageSet[], int count, const SkPoint dstClips[],
+                              const SkMatrix preViewMatrices[], const SkPaint* paint,
+                              SrcRectConstraint constraint) override {
+        int dstClipCount = 0;
+        int preViewMatrixCount = 0;
+        for (int i = 0; i < count; ++i) {
+            if (imageSet[i].fHasClip) {
+                dstClipCount += 4;
+            }
+            preViewMatrixCount = SkTMax(preViewMatrixCount, imageSet[i].fMatrixIndex + 1);
+        }
+        fBackend.drawEdgeAAImageSet(fBackend.backend, imageSet, count, dstClips, dstClipCount,
+                                    preViewMatrices, preViewMatrixCount, paint, constraint);
+    }
+
+    void onDrawPicture(const SkPicture* picture, const SkMatrix* matrix, const SkPaint* paint) override {
+        SkCanvas::onDrawPicture(picture, matrix, paint);
+    }
+
+    void onDrawDrawable(SkDrawable* drawable, const SkMatrix* matrix) override {
+        SkCanvas::onDrawDrawable(drawable, matrix);
+    }
+
+private:
+    const RustCanvasBackend fBackend;
+};
+
+extern "C" SkCanvas* C_RustCanvas_new(int width, int height, const RustCanvasBackend* backend) {
+    return new RustCanvas(width, height, *backend);
+}
+
+extern "C" void C_SkCanvas_ImageSetEntry_get(const SkCanvas::ImageSetEntry* imageSet, int index, const SkImage** image,
+                                             SkRect* srcRect, SkRect* dstRect, int* matrixIndex, float* alpha,
+                                             unsigned* aaFlags, bool* hasClip) {
+    auto& entry = imageSet[index];
+    *image = entry.fImage.get();
+    *srcRect = entry.fSrcRect;
+    *dstRect = entry.fDstRect;
+    *matrixIndex = entry.fMatrixIndex;
+    *alpha = entry.fAlpha;
+    *aaFlags = entry.fAAFlags;
+    *hasClip = entry.fHasClip;
+}
+
 #if defined(SK_VULKAN)
 
 // The GrVkBackendContext struct binding's length is too short
@@ -2248,4 +3282,487 @@ extern "C" SkCanvas* C_SkSVGCanvas_Make(const SkRect* bounds, SkWStream* writer)
     return SkSVGCanvas::Make(*bounds, writer).release();
 }
 
+//
+// experimental/svg/model/SkSVGDOM.h
+//
+
+extern "C" SkSVGDOM* C_SkSVGDOM_MakeFromStream(SkStream* stream) {
+    return SkSVGDOM::MakeFromStream(*stream).release();
+}
+
+extern "C" void C_SkSVGDOM_containerSize(const SkSVGDOM* self, SkSize* size) {
+    *size = self->containerSize();
+}
+
+extern "C" void C_SkSVGDOM_setContainerSize(SkSVGDOM* self, const SkSize* size) {
+    self->setContainerSize(*size);
+}
+
+extern "C" void C_SkSVGDOM_render(const SkSVGDOM* self, SkCanvas* canvas) {
+    self->render(canvas);
+}
+
+#endif
+
+#if defined(SK_ENABLE_SKOTTIE)
+
+//
+// modules/skottie/include/Skottie.h
+//
+
+extern "C" skottie::Animation* C_skottie_Animation_Make(const char* data, size_t length) {
+    return skottie::Animation::Make(data, length).release();
+}
+
+// resourceProvider and propertyObserver are adopted, fontMgr is shared.
+extern "C" skottie::Animation* C_skottie_Animation_Builder_make(
+        const char* data, size_t length,
+        skottie::ResourceProvider* resourceProvider,
+        skottie::PropertyObserver* propertyObserver,
+        const SkFontMgr* fontMgr) {
+    skottie::Animation::Builder builder;
+    builder.setResourceProvider(sk_sp<skottie::ResourceProvider>(resourceProvider));
+    builder.setPropertyObserver(sk_sp<skottie::PropertyObserver>(propertyObserver));
+    builder.setFontManager(spFromConst(fontMgr));
+    return builder.make(data, length).release();
+}
+
+extern "C" void C_skottie_Animation_ref(const skottie::Animation* self) {
+    self->ref();
+}
+
+extern "C" void C_skottie_Animation_unref(const skottie::Animation* self) {
+    self->unref();
+}
+
+extern "C" bool C_skottie_Animation_unique(const skottie::Animation* self) {
+    return self->unique();
+}
+
+extern "C" void C_skottie_Animation_render(const skottie::Animation* self, SkCanvas* canvas, const SkRect* dst) {
+    self->render(canvas, dst);
+}
+
+extern "C" void C_skottie_Animation_seek(skottie::Animation* self, SkScalar t) {
+    self->seek(t);
+}
+
+extern "C" SkScalar C_skottie_Animation_duration(const skottie::Animation* self) {
+    return self->duration();
+}
+
+extern "C" void C_skottie_Animation_size(const skottie::Animation* self, SkSize* size) {
+    *size = self->size();
+}
+
+// skottie::Animation does not expose the frame rate and the in / out points,
+// so we parse them from the JSON the same way Skottie does.
+extern "C" bool C_skottie_ReadAnimationTiming(const char* data, size_t length, SkScalar* fps, SkScalar* inPoint, SkScalar* outPoint) {
+    const skjson::DOM dom(data, length);
+    const skjson::ObjectValue* json = dom.root();
+    if (!json) {
+        return false;
+    }
+    *fps = skottie::ParseDefault<float>((*json)["fr"], -1.0f);
+    *inPoint = skottie::ParseDefault<float>((*json)["ip"], 0.0f);
+    *outPoint = SkTMax(skottie::ParseDefault<float>((*json)["op"], SK_ScalarMax), *inPoint);
+    return true;
+}
+
+//
+// RustResourceProvider : public skottie::ResourceProvider
+//
+
+typedef SkData* (*RustResourceProviderLoad)(void* provider, const char* resourcePath, const char* resourceName);
+typedef SkData* (*RustResourceProviderLoadFont)(void* provider, const char* name, const char* url);
+
+// An image asset of a single, already decoded image.
+class StaticImageAsset : public skottie::ImageAsset {
+public:
+    explicit StaticImageAsset(sk_sp<SkImage> image) : fImage(std::move(image)) {}
+
+    bool isMultiFrame() override { return false; }
+
+    sk_sp<SkImage> getFrame(float) override { return fImage; }
+
+private:
+    const sk_sp<SkImage> fImage;
+};
+
+class RustResourceProvider : public skottie::ResourceProvider {
+public:
+    RustResourceProvider(void* provider, RustResourceProviderLoad load, RustResourceProviderLoadFont loadFont)
+        : fProvider(provider), fLoad(load), fLoadFont(loadFont) {}
+
+    sk_sp<SkData> load(const char resource_path[], const char resource_name[]) const override {
+        return sk_sp<SkData>(fLoad(fProvider, resource_path, resource_name));
+    }
+
+    sk_sp<skottie::ImageAsset> loadImageAsset(const char resource_path[], const char resource_name[]) const override {
+        auto image = SkImage::MakeFromEncoded(this->load(resource_path, resource_name));
+        if (!image) {
+            return nullptr;
+        }
+        return sk_make_sp<StaticImageAsset>(std::move(image));
+    }
+
+    sk_sp<SkData> loadFont(const char name[], const char url[]) const override {
+        return sk_sp<SkData>(fLoadFont(fProvider, name, url));
+    }
+
+private:
+    void* fProvider;
+    RustResourceProviderLoad fLoad;
+    RustResourceProviderLoadFont fLoadFont;
+};
+
+extern "C" skottie::ResourceProvider* C_RustResourceProvider_new(
+        void* provider, RustResourceProviderLoad load, RustResourceProviderLoadFont loadFont) {
+    return new RustResourceProvider(provider, load, loadFont);
+}
+
+//
+// modules/skottie/include/SkottieProperty.h
+//
+
+extern "C" void C_skottie_ColorPropertyHandle_delete(skottie::ColorPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" SkColor C_skottie_ColorPropertyHandle_get(const skottie::ColorPropertyHandle* self) {
+    return self->get();
+}
+
+extern "C" void C_skottie_ColorPropertyHandle_set(skottie::ColorPropertyHandle* self, SkColor color) {
+    self->set(color);
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_delete(skottie::OpacityPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" float C_skottie_OpacityPropertyHandle_get(const skottie::OpacityPropertyHandle* self) {
+    return self->get();
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_set(skottie::OpacityPropertyHandle* self, float opacity) {
+    self->set(opacity);
+}
+
+extern "C" void C_skottie_TextPropertyHandle_delete(skottie::TextPropertyHandle* self) {
+    delete self;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_getText(const skottie::TextPropertyHandle* self, SkString* text) {
+    *text = self->get().fText;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setText(skottie::TextPropertyHandle* self, const char* text, size_t length) {
+    auto value = self->get();
+    value.fText.set(text, length);
+    self->set(value);
+}
+
+extern "C" float C_skottie_TextPropertyHandle_getTextSize(const skottie::TextPropertyHandle* self) {
+    return self->get().fTextSize;
+}
+
+extern "C" void C_skottie_TextPropertyHandle_setTextSize(skottie::TextPropertyHandle* self, float textSize) {
+    auto value = self->get();
+    value.fTextSize = textSize;
+    self->set(value);
+}
+
+//
+// RustPropertyObserver : public skottie::PropertyObserver
+//
+
+// The handles passed to the callbacks are owned by the Rust side.
+typedef void (*RustPropertyObserverOnColor)(void* observer, const char* nodeName, skottie::ColorPropertyHandle* handle);
+typedef void (*RustPropertyObserverOnOpacity)(void* observer, const char* nodeName, skottie::OpacityPropertyHandle* handle);
+typedef void (*RustPropertyObserverOnText)(void* observer, const char* nodeName, skottie::TextPropertyHandle* handle);
+
+class RustPropertyObserver : public skottie::PropertyObserver {
+public:
+    RustPropertyObserver(
+            void* observer,
+            RustPropertyObserverOnColor onColor,
+            RustPropertyObserverOnOpacity onOpacity,
+            RustPropertyObserverOnText onText)
+        : fObserver(observer), fOnColor(onColor), fOnOpacity(onOpacity), fOnText(onText) {}
+
+    void onColorProperty(const char node_name[], const LazyHandle<skottie::ColorPropertyHandle>& lh) override {
+        fOnColor(fObserver, node_name, lh().release());
+    }
+
+    void onOpacityProperty(const char node_name[], const LazyHandle<skottie::OpacityPropertyHandle>& lh) override {
+        fOnOpacity(fObserver, node_name, lh().release());
+    }
+
+    void onTextProperty(const char node_name[], const LazyHandle<skottie::TextPropertyHandle>& lh) override {
+        fOnText(fObserver, node_name, lh().release());
+    }
+
+private:
+    void* fObserver;
+    RustPropertyObserverOnColor fOnColor;
+    RustPropertyObserverOnOpacity fOnOpacity;
+    RustPropertyObserverOnText fOnText;
+};
+
+extern "C" skottie::PropertyObserver* C_RustPropertyObserver_new(
+        void* observer,
+        RustPropertyObserverOnColor onColor,
+        RustPropertyObserverOnOpacity onOpacity,
+        RustPropertyObserverOnText onText) {
+    return new RustPropertyObserver(observer, onColor, onOpacity, onText);
+}
+
+#endif
+
+#if defined(SK_ENABLE_PARTICLES)
+
+//
+// modules/particles/include/SkParticleEffect.h
+//
+
+extern "C" SkParticleEffectParams* C_SkParticleEffectParams_MakeFromJSON(const char* data, size_t length) {
+    // the affector and drawable types need to be registered before effects can be deserialized.
+    static bool registered = (SkParticleAffector::RegisterAffectorTypes(), SkParticleDrawable::RegisterDrawableTypes(), true);
+    (void)registered;
+
+    const skjson::DOM dom(data, length);
+    if (dom.root().getType() != skjson::Value::Type::kObject) {
+        return nullptr;
+    }
+    SkFromJsonVisitor fromJson(dom.root());
+    auto params = sk_make_sp<SkParticleEffectParams>();
+    params->visitFields(&fromJson);
+    return params.release();
+}
+
+extern "C" void C_SkParticleEffectParams_ref(const SkParticleEffectParams* self) {
+    self->ref();
+}
+
+extern "C" void C_SkParticleEffectParams_unref(const SkParticleEffectParams* self) {
+    self->unref();
+}
+
+extern "C" bool C_SkParticleEffectParams_unique(const SkParticleEffectParams* self) {
+    return self->unique();
+}
+
+extern "C" SkParticleEffect* C_SkParticleEffect_Make(const SkParticleEffectParams* params, uint32_t seed) {
+    return new SkParticleEffect(spFromConst(params), SkRandom(seed));
+}
+
+extern "C" void C_SkParticleEffect_ref(const SkParticleEffect* self) {
+    self->ref();
+}
+
+extern "C" void C_SkParticleEffect_unref(const SkParticleEffect* self) {
+    self->unref();
+}
+
+extern "C" bool C_SkParticleEffect_unique(const SkParticleEffect* self) {
+    return self->unique();
+}
+
+extern "C" void C_SkParticleEffect_start(SkParticleEffect* self, double now, bool looping) {
+    self->start(now, looping);
+}
+
+extern "C" void C_SkParticleEffect_update(SkParticleEffect* self, double now) {
+    self->update(now);
+}
+
+extern "C" void C_SkParticleEffect_draw(SkParticleEffect* self, SkCanvas* canvas) {
+    self->draw(canvas);
+}
+
+extern "C" bool C_SkParticleEffect_isAlive(const SkParticleEffect* self) {
+    return self->isAlive();
+}
+
+extern "C" void C_SkParticleEffect_setParams(SkParticleEffect* self, const SkParticleEffectParams* params) {
+    self->setParams(spFromConst(params));
+}
+
+#endif
+
+#if defined(SK_ENABLE_ICU)
+
+//
+// third_party/icu/SkLoadICU.h
+//
+
+// Loads the ICU data on Windows, must be called before any other ICU function is used.
+extern "C" bool C_SkLoadICU() {
+    return SkLoadICU();
+}
+
+//
+// third_party/externals/icu/source/common/unicode/ubidi.h
+//
+
+// Returns nullptr if the bidi algorithm fails, text must outlive the returned object.
+extern "C" UBiDi* C_ubidi_open(const UChar* text, int32_t length, UBiDiLevel paragraphLevel) {
+    UErrorCode status = U_ZERO_ERROR;
+    UBiDi* bidi = ubidi_openSized(length, 0, &status);
+    if (U_FAILURE(status)) {
+        return nullptr;
+    }
+    ubidi_setPara(bidi, text, length, paragraphLevel, nullptr, &status);
+    if (U_FAILURE(status)) {
+        ubidi_close(bidi);
+        return nullptr;
+    }
+    return bidi;
+}
+
+extern "C" void C_ubidi_close(UBiDi* self) {
+    ubidi_close(self);
+}
+
+extern "C" int32_t C_ubidi_countRuns(UBiDi* self) {
+    UErrorCode status = U_ZERO_ERROR;
+    int32_t count = ubidi_countRuns(self, &status);
+    return U_SUCCESS(status) ? count : 0;
+}
+
+extern "C" bool C_ubidi_getVisualRun(UBiDi* self, int32_t runIndex, int32_t* logicalStart, int32_t* length) {
+    return ubidi_getVisualRun(self, runIndex, logicalStart, length) == UBIDI_RTL;
+}
+
+extern "C" UBiDiLevel C_ubidi_getLevelAt(const UBiDi* self, int32_t charIndex) {
+    return ubidi_getLevelAt(self, charIndex);
+}
+
+//
+// third_party/externals/icu/source/common/unicode/ubrk.h
+//
+
+// Returns nullptr if the break iterator can not be created, text must outlive the returned object.
+extern "C" UBreakIterator* C_ubrk_openLine(const UChar* text, int32_t length) {
+    UErrorCode status = U_ZERO_ERROR;
+    UBreakIterator* iterator = ubrk_open(UBRK_LINE, uloc_getDefault(), text, length, &status);
+    return U_SUCCESS(status) ? iterator : nullptr;
+}
+
+extern "C" void C_ubrk_close(UBreakIterator* self) {
+    ubrk_close(self);
+}
+
+// Returns -1 if there are no more breaks.
+extern "C" int32_t C_ubrk_next(UBreakIterator* self, bool* isHard) {
+    int32_t position = ubrk_next(self);
+    int32_t status = ubrk_getRuleStatus(self);
+    *isHard = status >= UBRK_LINE_HARD && status < UBRK_LINE_HARD_LIMIT;
+    return position == UBRK_DONE ? -1 : position;
+}
+
+#endif
+
+#if defined(SK_ENABLE_SKSHAPER)
+
+//
+// modules/skshaper/include/SkShaper.h
+//
+
+extern "C" SkShaper* C_SkShaper_Make() {
+    return SkShaper::Make().release();
+}
+
+extern "C" SkShaper* C_SkShaper_MakePrimitive() {
+    return SkShaper::MakePrimitive().release();
+}
+
+extern "C" SkShaper* C_SkShaper_MakeHarfBuzz() {
+    return SkShaper::MakeHarfBuzz().release();
+}
+
+extern "C" void C_SkShaper_delete(SkShaper* self) {
+    delete self;
+}
+
+extern "C" void C_SkShaper_shape(
+        const SkShaper* self, SkShaper::RunHandler* handler, const SkFont* font,
+        const char* utf8, size_t utf8Bytes, bool leftToRight, const SkPoint* point, SkScalar width,
+        SkPoint* endPoint) {
+    *endPoint = self->shape(handler, *font, utf8, utf8Bytes, leftToRight, *point, width);
+}
+
+extern "C" SkShaper::RunHandler* C_SkTextBlobBuilderRunHandler_new(const char* utf8) {
+    return new SkTextBlobBuilderRunHandler(utf8);
+}
+
+extern "C" SkTextBlob* C_SkTextBlobBuilderRunHandler_makeBlob(SkShaper::RunHandler* self) {
+    return static_cast<SkTextBlobBuilderRunHandler*>(self)->makeBlob().release();
+}
+
+extern "C" void C_SkShaper_RunHandler_delete(SkShaper::RunHandler* self) {
+    delete self;
+}
+
+//
+// RustRunHandler : public SkShaper::RunHandler
+//
+
+typedef void (*RustRunHandlerCommitRun)(
+        void* handler,
+        const SkFont* font, const SkVector* advance, SkScalar ascent, SkScalar descent, SkScalar leading,
+        size_t utf8Begin, size_t utf8End,
+        size_t glyphCount, const SkGlyphID* glyphs, const SkPoint* positions, const uint32_t* clusters);
+typedef void (*RustRunHandlerCommitLine)(void* handler);
+
+// Collects the glyphs of a run in buffers and passes them to Rust when the run gets committed.
+class RustRunHandler : public SkShaper::RunHandler {
+public:
+    RustRunHandler(void* handler, const char* utf8, RustRunHandlerCommitRun commitRun, RustRunHandlerCommitLine commitLine)
+        : fHandler(handler), fUtf8(utf8), fCommitRun(commitRun), fCommitLine(commitLine), fInfo{} {}
+
+    Buffer newRunBuffer(const RunInfo& info, const SkFont& font, int glyphCount, SkSpan<const char> utf8) override {
+        fInfo = info;
+        fFont = font;
+        fUtf8Begin = utf8.data() - fUtf8;
+        fUtf8End = fUtf8Begin + utf8.size();
+        fGlyphs.resize(glyphCount);
+        fPositions.resize(glyphCount);
+        fClusters.resize(glyphCount);
+        return { fGlyphs.data(), fPositions.data(), fClusters.data() };
+    }
+
+    void commitRun() override {
+        fCommitRun(
+            fHandler,
+            &fFont, &fInfo.fAdvance, fInfo.fAscent, fInfo.fDescent, fInfo.fLeading,
+            fUtf8Begin, fUtf8End,
+            fGlyphs.size(), fGlyphs.data(), fPositions.data(), fClusters.data());
+    }
+
+    void commitLine() override {
+        fCommitLine(fHandler);
+    }
+
+private:
+    void* fHandler;
+    const char* fUtf8;
+    RustRunHandlerCommitRun fCommitRun;
+    RustRunHandlerCommitLine fCommitLine;
+
+    RunInfo fInfo;
+    SkFont fFont;
+    size_t fUtf8Begin = 0;
+    size_t fUtf8End = 0;
+    std::vector<SkGlyphID> fGlyphs;
+    std::vector<SkPoint> fPositions;
+    std::vector<uint32_t> fClusters;
+};
+
+extern "C" SkShaper::RunHandler* C_RustRunHandler_new(
+        void* handler, const char* utf8, RustRunHandlerCommitRun commitRun, RustRunHandlerCommitLine commitLine) {
+    return new RustRunHandler(handler, utf8, commitRun, commitLine);
+}
+
 #endif
@@ -1,4 +1,11 @@
-// TODO: wrap SkAndroidCodec.h, SkCodec.h, SkCodecAnimation.h
+pub mod android_codec;
+pub use android_codec::AndroidCodec;
+
+#[allow(clippy::module_inception)]
+pub mod codec;
+pub use codec::Codec;
+
+pub mod codec_animation;
 
 mod encoded_origin;
 pub use encoded_origin::*;
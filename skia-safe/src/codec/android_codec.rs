@@ -0,0 +1,255 @@
+use crate::codec::codec::{Result, ZeroInitialized};
+use crate::prelude::*;
+use crate::{Codec, Data, EncodedImageFormat, IRect, ISize, ImageInfo, Pixmap};
+use skia_bindings::{
+    C_SkAndroidCodec_MakeFromCodec, C_SkAndroidCodec_MakeFromData,
+    C_SkAndroidCodec_computeSampleSize, C_SkAndroidCodec_delete, C_SkAndroidCodec_getAndroidPixels,
+    C_SkAndroidCodec_getEncodedFormat, C_SkAndroidCodec_getInfo,
+    C_SkAndroidCodec_getSampledDimensions, C_SkAndroidCodec_getSampledSubsetDimensions,
+    C_SkAndroidCodec_getSupportedSubset, SkAndroidCodec,
+};
+use std::mem;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AndroidOptions {
+    pub zero_initialized: ZeroInitialized,
+    pub subset: Option<IRect>,
+    /// Decode to 1/sample_size of the original width and height.
+    pub sample_size: usize,
+}
+
+impl Default for AndroidOptions {
+    fn default() -> Self {
+        AndroidOptions {
+            zero_initialized: ZeroInitialized::No,
+            subset: None,
+            sample_size: 1,
+        }
+    }
+}
+
+/// A codec that supports down-sampled and subset decoding for all image formats.
+pub struct AndroidCodec(*mut SkAndroidCodec);
+
+impl NativeAccess<SkAndroidCodec> for AndroidCodec {
+    fn native(&self) -> &SkAndroidCodec {
+        unsafe { &*self.0 }
+    }
+
+    fn native_mut(&mut self) -> &mut SkAndroidCodec {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl Drop for AndroidCodec {
+    fn drop(&mut self) {
+        unsafe { C_SkAndroidCodec_delete(self.0) }
+    }
+}
+
+impl AndroidCodec {
+    pub fn from_data(data: &Data) -> Option<AndroidCodec> {
+        unsafe { C_SkAndroidCodec_MakeFromData(data.shared_native()) }
+            .to_option()
+            .map(AndroidCodec)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<AndroidCodec> {
+        Self::from_data(&Data::new_copy(bytes))
+    }
+
+    pub fn from_codec(mut codec: Codec) -> Option<AndroidCodec> {
+        // the codec is consumed by the AndroidCodec.
+        let codec_ptr = codec.native_mut() as *mut _;
+        mem::forget(codec);
+        unsafe { C_SkAndroidCodec_MakeFromCodec(codec_ptr) }
+            .to_option()
+            .map(AndroidCodec)
+    }
+
+    pub fn info(&self) -> ImageInfo {
+        let mut info = ImageInfo::default();
+        unsafe { C_SkAndroidCodec_getInfo(self.native(), info.native_mut()) };
+        info
+    }
+
+    pub fn dimensions(&self) -> ISize {
+        self.info().dimensions()
+    }
+
+    pub fn encoded_format(&self) -> EncodedImageFormat {
+        EncodedImageFormat::from_native(unsafe { C_SkAndroidCodec_getEncodedFormat(self.native()) })
+    }
+
+    /// Returns the sample size that decodes the image closest to desired_size, and the
+    /// dimensions the image is decoded to with that sample size.
+    pub fn compute_sample_size(&self, desired_size: impl Into<ISize>) -> (usize, ISize) {
+        let mut size = desired_size.into();
+        let sample_size =
+            unsafe { C_SkAndroidCodec_computeSampleSize(self.native(), size.native_mut()) };
+        (sample_size.try_into().unwrap(), size)
+    }
+
+    pub fn get_sampled_dimensions(&self, sample_size: usize) -> ISize {
+        ISize::from_native(unsafe {
+            C_SkAndroidCodec_getSampledDimensions(self.native(), sample_size.try_into().unwrap())
+        })
+    }
+
+    pub fn get_supported_subset(&self, desired_subset: impl AsRef<IRect>) -> Option<IRect> {
+        let mut subset = *desired_subset.as_ref();
+        unsafe { C_SkAndroidCodec_getSupportedSubset(self.native(), subset.native_mut()) }
+            .if_true_some(subset)
+    }
+
+    pub fn get_sampled_subset_dimensions(
+        &self,
+        sample_size: usize,
+        subset: impl AsRef<IRect>,
+    ) -> ISize {
+        ISize::from_native(unsafe {
+            C_SkAndroidCodec_getSampledSubsetDimensions(
+                self.native(),
+                sample_size.try_into().unwrap(),
+                subset.as_ref().native(),
+            )
+        })
+    }
+
+    /// Decodes the image into pixels, info must have the dimensions returned by
+    /// get_sampled_dimensions() or get_sampled_subset_dimensions() for the options used.
+    #[must_use]
+    pub fn get_android_pixels(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &mut [u8],
+        row_bytes: usize,
+        options: Option<&AndroidOptions>,
+    ) -> Result {
+        if pixels.len() < info.compute_byte_size(row_bytes) {
+            return Result::InvalidParameters;
+        }
+        unsafe {
+            self.get_android_pixels_unchecked(info, pixels.as_mut_ptr() as _, row_bytes, options)
+        }
+    }
+
+    #[must_use]
+    pub fn get_android_pixels_to_pixmap(
+        &mut self,
+        pixmap: &mut Pixmap,
+        options: Option<&AndroidOptions>,
+    ) -> Result {
+        unsafe {
+            self.get_android_pixels_unchecked(
+                pixmap.info(),
+                pixmap.writable_addr(),
+                pixmap.row_bytes(),
+                options,
+            )
+        }
+    }
+
+    unsafe fn get_android_pixels_unchecked(
+        &mut self,
+        info: &ImageInfo,
+        pixels: *mut std::ffi::c_void,
+        row_bytes: usize,
+        options: Option<&AndroidOptions>,
+    ) -> Result {
+        let default_options = AndroidOptions::default();
+        let options = options.unwrap_or(&default_options);
+        Result::from_native(C_SkAndroidCodec_getAndroidPixels(
+            self.native_mut(),
+            info.native(),
+            pixels,
+            row_bytes,
+            options.zero_initialized.into_native(),
+            options.subset.native().as_ptr_or_null(),
+            options.sample_size.try_into().unwrap(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AndroidCodec, AndroidOptions};
+    use crate::codec::codec::Result;
+    use crate::{encode, Bitmap, Color, EncodedImageFormat, IRect, ImageInfo, Pixmap};
+
+    // 64x32, the left half is blue, the right half red.
+    fn encoded_png() -> Vec<u8> {
+        let mut bitmap = Bitmap::new();
+        bitmap.alloc_n32_pixels((64, 32), None);
+        bitmap.erase_color(Color::BLUE);
+        bitmap.erase(Color::RED, IRect::from_xywh(32, 0, 32, 32));
+        encode::bitmap(&bitmap, EncodedImageFormat::PNG, 100)
+            .unwrap()
+            .as_bytes()
+            .to_vec()
+    }
+
+    #[test]
+    fn sampled_decode() {
+        let mut codec = AndroidCodec::from_bytes(&encoded_png()).unwrap();
+        let dimensions = codec.get_sampled_dimensions(4);
+        assert_eq!(dimensions, (16, 8).into());
+        let info = ImageInfo::new_n32_premul(dimensions, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut pixmap = Pixmap::new(&info, &mut pixels, info.min_row_bytes());
+        let options = AndroidOptions {
+            sample_size: 4,
+            ..AndroidOptions::default()
+        };
+        let result = codec.get_android_pixels_to_pixmap(&mut pixmap, Some(&options));
+        assert_eq!(result, Result::Success);
+        assert_eq!(pixmap.get_color((0, 0)), Color::BLUE);
+        assert_eq!(pixmap.get_color((7, 7)), Color::BLUE);
+        assert_eq!(pixmap.get_color((8, 0)), Color::RED);
+        assert_eq!(pixmap.get_color((15, 7)), Color::RED);
+    }
+
+    #[test]
+    fn sampled_subset_decode() {
+        let mut codec = AndroidCodec::from_bytes(&encoded_png()).unwrap();
+        let subset = codec
+            .get_supported_subset(IRect::from_xywh(0, 0, 32, 16))
+            .unwrap();
+        let dimensions = codec.get_sampled_subset_dimensions(2, subset);
+        assert_eq!(dimensions, (16, 8).into());
+        let info = ImageInfo::new_n32_premul(dimensions, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let options = AndroidOptions {
+            subset: Some(subset),
+            sample_size: 2,
+            ..AndroidOptions::default()
+        };
+        let result =
+            codec.get_android_pixels(&info, &mut pixels, info.min_row_bytes(), Some(&options));
+        assert_eq!(result, Result::Success);
+        // the subset only covers the blue half.
+        let pixmap = Pixmap::new(&info, &pixels, info.min_row_bytes());
+        assert_eq!(pixmap.get_color((0, 0)), Color::BLUE);
+        assert_eq!(pixmap.get_color((15, 7)), Color::BLUE);
+
+        let subset = codec
+            .get_supported_subset(IRect::from_xywh(32, 16, 32, 16))
+            .unwrap();
+        assert_eq!(
+            codec.get_sampled_subset_dimensions(1, subset),
+            (32, 16).into()
+        );
+        let info = ImageInfo::new_n32_premul((32, 16), None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let options = AndroidOptions {
+            subset: Some(subset),
+            ..AndroidOptions::default()
+        };
+        let result =
+            codec.get_android_pixels(&info, &mut pixels, info.min_row_bytes(), Some(&options));
+        assert_eq!(result, Result::Success);
+        let pixmap = Pixmap::new(&info, &pixels, info.min_row_bytes());
+        assert_eq!(pixmap.get_color((0, 0)), Color::RED);
+        assert_eq!(pixmap.get_color((31, 15)), Color::RED);
+    }
+}
@@ -0,0 +1,622 @@
+use crate::codec::codec_animation::{Blend, DisposalMethod};
+use crate::prelude::*;
+use crate::{AlphaType, Data, EncodedImageFormat, EncodedOrigin, IRect, ISize, ImageInfo, Pixmap};
+use skia_bindings::{
+    C_IncrementalStream_append, C_IncrementalStream_finish, C_IncrementalStream_new,
+    C_SkCodec_MakeFromData, C_SkCodec_MakeFromStream, C_SkCodec_delete, C_SkCodec_getEncodedFormat,
+    C_SkCodec_getFrameCount, C_SkCodec_getFrameInfo, C_SkCodec_getICCProfile, C_SkCodec_getInfo,
+    C_SkCodec_getOrigin, C_SkCodec_getPixels, C_SkCodec_getRepetitionCount,
+    C_SkCodec_getScaledDimensions, C_SkCodec_getScanlineOrder, C_SkCodec_getScanlines,
+    C_SkCodec_getValidSubset, C_SkCodec_incrementalDecode, C_SkCodec_nextScanline,
+    C_SkCodec_outputScanline, C_SkCodec_skipScanlines, C_SkCodec_startIncrementalDecode,
+    C_SkCodec_startScanlineDecode, SkCodec, SkCodec_FrameInfo, SkCodec_Options, SkCodec_Result,
+    SkCodec_SkScanlineOrder, SkCodec_ZeroInitialized, SkStream,
+};
+use std::marker::PhantomData;
+use std::{ptr, slice};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum Result {
+    Success = SkCodec_Result::kSuccess as _,
+    IncompleteInput = SkCodec_Result::kIncompleteInput as _,
+    ErrorInInput = SkCodec_Result::kErrorInInput as _,
+    InvalidConversion = SkCodec_Result::kInvalidConversion as _,
+    InvalidScale = SkCodec_Result::kInvalidScale as _,
+    InvalidParameters = SkCodec_Result::kInvalidParameters as _,
+    InvalidInput = SkCodec_Result::kInvalidInput as _,
+    CouldNotRewind = SkCodec_Result::kCouldNotRewind as _,
+    InternalError = SkCodec_Result::kInternalError as _,
+    Unimplemented = SkCodec_Result::kUnimplemented as _,
+}
+
+impl NativeTransmutable<SkCodec_Result> for Result {}
+#[test]
+fn test_codec_result_layout() {
+    Result::test_layout()
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum ZeroInitialized {
+    Yes = SkCodec_ZeroInitialized::kYes_ZeroInitialized as _,
+    No = SkCodec_ZeroInitialized::kNo_ZeroInitialized as _,
+}
+
+impl NativeTransmutable<SkCodec_ZeroInitialized> for ZeroInitialized {}
+#[test]
+fn test_codec_zero_initialized_layout() {
+    ZeroInitialized::test_layout()
+}
+
+impl Default for ZeroInitialized {
+    fn default() -> Self {
+        ZeroInitialized::No
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum ScanlineOrder {
+    TopDown = SkCodec_SkScanlineOrder::kTopDown_SkScanlineOrder as _,
+    BottomUp = SkCodec_SkScanlineOrder::kBottomUp_SkScanlineOrder as _,
+}
+
+impl NativeTransmutable<SkCodec_SkScanlineOrder> for ScanlineOrder {}
+#[test]
+fn test_codec_scanline_order_layout() {
+    ScanlineOrder::test_layout()
+}
+
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Options {
+    pub zero_initialized: ZeroInitialized,
+    pub subset: Option<IRect>,
+    pub frame_index: usize,
+    pub prior_frame: Option<usize>,
+}
+
+impl Options {
+    // The native options point to the subset of self, so self must outlive the result.
+    // Frame indices that do not fit into Skia's int are invalid parameters.
+    fn to_native(&self) -> std::result::Result<SkCodec_Options, Result> {
+        let frame_index = |index: usize| index.try_into().map_err(|_| Result::InvalidParameters);
+        Ok(SkCodec_Options {
+            fZeroInitialized: self.zero_initialized.into_native(),
+            fSubset: self.subset.native().as_ptr_or_null(),
+            fFrameIndex: frame_index(self.frame_index)?,
+            fPriorFrame: match self.prior_frame {
+                Some(prior_frame) => frame_index(prior_frame)?,
+                None => -1,
+            },
+        })
+    }
+}
+
+/// Information about a single frame of an animated image.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct FrameInfo {
+    /// The frame that must be decoded before this one can be decoded on top of it,
+    /// None if this frame can be decoded independently.
+    pub required_frame: Option<usize>,
+    /// Number of milliseconds to show this frame.
+    pub duration: usize,
+    /// Whether the end marker for this frame was found in the data received so far.
+    pub fully_received: bool,
+    pub alpha_type: AlphaType,
+    /// How the frame should be modified before decoding the next one.
+    pub disposal_method: DisposalMethod,
+    /// How the frame is combined with the frame it is decoded on top of.
+    pub blend: Blend,
+}
+
+impl FrameInfo {
+    fn from_native(native: &SkCodec_FrameInfo, blend: Blend) -> FrameInfo {
+        FrameInfo {
+            required_frame: native.fRequiredFrame.try_into().ok(),
+            duration: native.fDuration.try_into().unwrap(),
+            fully_received: native.fFullyReceived,
+            alpha_type: AlphaType::from_native(native.fAlphaType),
+            disposal_method: DisposalMethod::from_native(native.fDisposalMethod),
+            blend,
+        }
+    }
+}
+
+pub struct Codec {
+    native: *mut SkCodec,
+    // The stream the codec reads from if it was created with new_incremental(), owned by the codec.
+    incremental_stream: *mut SkStream,
+}
+
+impl NativeAccess<SkCodec> for Codec {
+    fn native(&self) -> &SkCodec {
+        unsafe { &*self.native }
+    }
+
+    fn native_mut(&mut self) -> &mut SkCodec {
+        unsafe { &mut *self.native }
+    }
+}
+
+impl Drop for Codec {
+    fn drop(&mut self) {
+        unsafe { C_SkCodec_delete(self.native) }
+    }
+}
+
+impl Codec {
+    pub fn from_data(data: &Data) -> Option<Codec> {
+        unsafe { C_SkCodec_MakeFromData(data.shared_native()) }
+            .to_option()
+            .map(|native| Codec {
+                native,
+                incremental_stream: ptr::null_mut(),
+            })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Codec> {
+        Self::from_data(&Data::new_copy(bytes))
+    }
+
+    /// Creates a codec from the first bytes of an encoded image, more bytes can be
+    /// provided later with append_bytes().
+    ///
+    /// Returns Result::IncompleteInput if the bytes do not contain the complete header yet.
+    pub fn new_incremental(bytes: &[u8]) -> std::result::Result<Codec, Result> {
+        unsafe {
+            let stream = C_IncrementalStream_new();
+            C_IncrementalStream_append(stream, bytes.as_ptr() as _, bytes.len());
+            let mut result = Result::Success;
+            C_SkCodec_MakeFromStream(stream, result.native_mut())
+                .to_option()
+                .map(|native| Codec {
+                    native,
+                    incremental_stream: stream,
+                })
+                .ok_or(result)
+        }
+    }
+
+    pub fn is_incremental(&self) -> bool {
+        !self.incremental_stream.is_null()
+    }
+
+    /// Provides more encoded bytes to a codec created with new_incremental().
+    ///
+    /// Panics if the codec was not created with new_incremental().
+    pub fn append_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        assert!(self.is_incremental());
+        unsafe {
+            C_IncrementalStream_append(self.incremental_stream, bytes.as_ptr() as _, bytes.len())
+        }
+        self
+    }
+
+    /// Marks the end of the encoded data after the last call to append_bytes().
+    ///
+    /// Panics if the codec was not created with new_incremental().
+    pub fn mark_complete(&mut self) -> &mut Self {
+        assert!(self.is_incremental());
+        unsafe { C_IncrementalStream_finish(self.incremental_stream) }
+        self
+    }
+
+    pub fn info(&self) -> ImageInfo {
+        let mut info = ImageInfo::default();
+        unsafe { C_SkCodec_getInfo(self.native(), info.native_mut()) };
+        info
+    }
+
+    pub fn dimensions(&self) -> ISize {
+        self.info().dimensions()
+    }
+
+    pub fn bounds(&self) -> IRect {
+        IRect::from_size(self.dimensions())
+    }
+
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        let mut size = 0;
+        unsafe {
+            C_SkCodec_getICCProfile(self.native(), &mut size)
+                .to_option()
+                .map(|bytes| slice::from_raw_parts(bytes, size))
+        }
+    }
+
+    pub fn origin(&self) -> EncodedOrigin {
+        EncodedOrigin::from_native(unsafe { C_SkCodec_getOrigin(self.native()) })
+    }
+
+    pub fn get_scaled_dimensions(&self, desired_scale: f32) -> ISize {
+        ISize::from_native(unsafe { C_SkCodec_getScaledDimensions(self.native(), desired_scale) })
+    }
+
+    pub fn get_valid_subset(&self, desired_subset: impl AsRef<IRect>) -> Option<IRect> {
+        let mut subset = *desired_subset.as_ref();
+        unsafe { C_SkCodec_getValidSubset(self.native(), subset.native_mut()) }.if_true_some(subset)
+    }
+
+    pub fn encoded_format(&self) -> EncodedImageFormat {
+        EncodedImageFormat::from_native(unsafe { C_SkCodec_getEncodedFormat(self.native()) })
+    }
+
+    #[must_use]
+    pub fn get_pixels(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &mut [u8],
+        row_bytes: usize,
+        options: Option<&Options>,
+    ) -> Result {
+        if pixels.len() < info.compute_byte_size(row_bytes) {
+            return Result::InvalidParameters;
+        }
+        let options = match options.map(Options::to_native).transpose() {
+            Ok(options) => options,
+            Err(result) => return result,
+        };
+        Result::from_native(unsafe {
+            C_SkCodec_getPixels(
+                self.native_mut(),
+                info.native(),
+                pixels.as_mut_ptr() as _,
+                row_bytes,
+                options.as_ptr_or_null(),
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn get_pixels_to_pixmap(
+        &mut self,
+        pixmap: &mut Pixmap,
+        options: Option<&Options>,
+    ) -> Result {
+        let options = match options.map(Options::to_native).transpose() {
+            Ok(options) => options,
+            Err(result) => return result,
+        };
+        Result::from_native(unsafe {
+            C_SkCodec_getPixels(
+                self.native_mut(),
+                pixmap.info().native(),
+                pixmap.writable_addr(),
+                pixmap.row_bytes(),
+                options.as_ptr_or_null(),
+            )
+        })
+    }
+
+    /// Decodes the frame frame_index of an animated image into pixmap.
+    ///
+    /// If prior_frame is set, pixmap must already contain that frame, and the decoder
+    /// draws on top of it. Otherwise the frames the frame depends on are decoded first.
+    #[must_use]
+    pub fn get_frame_to_pixmap(
+        &mut self,
+        pixmap: &mut Pixmap,
+        frame_index: usize,
+        prior_frame: Option<usize>,
+    ) -> Result {
+        let options = Options {
+            frame_index,
+            prior_frame,
+            ..Options::default()
+        };
+        self.get_pixels_to_pixmap(pixmap, Some(&options))
+    }
+
+    pub fn frame_count(&mut self) -> usize {
+        unsafe { C_SkCodec_getFrameCount(self.native_mut()) }
+            .try_into()
+            .unwrap()
+    }
+
+    pub fn get_frame_info(&self, index: usize) -> Option<FrameInfo> {
+        let index = index.try_into().ok()?;
+        let mut info = SkCodec_FrameInfo {
+            fRequiredFrame: -1,
+            fDuration: 0,
+            fFullyReceived: false,
+            fAlphaType: AlphaType::Unknown.into_native(),
+            fDisposalMethod: DisposalMethod::Keep.into_native(),
+        };
+        let mut blend = Blend::PriorFrame;
+        unsafe { C_SkCodec_getFrameInfo(self.native(), index, &mut info, blend.native_mut()) }
+            .if_true_then_some(|| FrameInfo::from_native(&info, blend))
+    }
+
+    pub fn frame_infos(&mut self) -> Vec<FrameInfo> {
+        (0..self.frame_count())
+            .filter_map(|i| self.get_frame_info(i))
+            .collect()
+    }
+
+    /// Returns the number of times the animation should be repeated after it was shown
+    /// once, or None if it should be repeated forever.
+    pub fn repetition_count(&mut self) -> Option<usize> {
+        unsafe { C_SkCodec_getRepetitionCount(self.native_mut()) }
+            .try_into()
+            .ok()
+    }
+
+    /// Prepares the decoding of the image into pixels while its data may not be complete yet.
+    ///
+    /// The decoding itself is done by calling decode() on the returned decoder.
+    pub fn start_incremental_decode<'a>(
+        &'a mut self,
+        info: &ImageInfo,
+        pixels: &'a mut [u8],
+        row_bytes: usize,
+        options: Option<&Options>,
+    ) -> std::result::Result<IncrementalDecoder<'a>, Result> {
+        if pixels.len() < info.compute_byte_size(row_bytes) {
+            return Err(Result::InvalidParameters);
+        }
+        // the codec keeps a pointer to the subset until the decoding is finished.
+        let options = Box::new(options.cloned().unwrap_or_default());
+        let native_options = options.to_native()?;
+        let result = Result::from_native(unsafe {
+            C_SkCodec_startIncrementalDecode(
+                self.native_mut(),
+                info.native(),
+                pixels.as_mut_ptr() as _,
+                row_bytes,
+                &native_options,
+            )
+        });
+        match result {
+            Result::Success => Ok(IncrementalDecoder {
+                codec: self,
+                _options: options,
+                pd: PhantomData,
+            }),
+            _ => Err(result),
+        }
+    }
+
+    pub fn start_scanline_decode(
+        &mut self,
+        info: &ImageInfo,
+        options: Option<&Options>,
+    ) -> std::result::Result<ScanlineDecoder, Result> {
+        let options = Box::new(options.cloned().unwrap_or_default());
+        let native_options = options.to_native()?;
+        let result = Result::from_native(unsafe {
+            C_SkCodec_startScanlineDecode(self.native_mut(), info.native(), &native_options)
+        });
+        match result {
+            Result::Success => Ok(ScanlineDecoder {
+                codec: self,
+                row_bytes: info.min_row_bytes(),
+                _options: options,
+            }),
+            _ => Err(result),
+        }
+    }
+}
+
+/// An incremental decoding in progress, created by Codec::start_incremental_decode().
+pub struct IncrementalDecoder<'a> {
+    codec: &'a mut Codec,
+    _options: Box<Options>,
+    pd: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> IncrementalDecoder<'a> {
+    /// Decodes as many rows as possible from the data available.
+    ///
+    /// If the result is IncompleteInput or ErrorInInput, the number of rows that were
+    /// initialized is returned, too.
+    pub fn decode(&mut self) -> (Result, Option<usize>) {
+        let mut rows_decoded = 0;
+        let result = Result::from_native(unsafe {
+            C_SkCodec_incrementalDecode(self.codec.native_mut(), &mut rows_decoded)
+        });
+        match result {
+            Result::IncompleteInput | Result::ErrorInInput => {
+                (result, Some(rows_decoded.try_into().unwrap()))
+            }
+            _ => (result, None),
+        }
+    }
+
+    pub fn append_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.codec.append_bytes(bytes);
+        self
+    }
+
+    pub fn mark_complete(&mut self) -> &mut Self {
+        self.codec.mark_complete();
+        self
+    }
+}
+
+/// A scanline decoding in progress, created by Codec::start_scanline_decode().
+pub struct ScanlineDecoder<'a> {
+    codec: &'a mut Codec,
+    row_bytes: usize,
+    _options: Box<Options>,
+}
+
+impl<'a> ScanlineDecoder<'a> {
+    /// Writes the next count_lines scanlines into dst and returns the number of lines
+    /// that were decoded successfully.
+    pub fn get_scanlines(&mut self, dst: &mut [u8], count_lines: usize, row_bytes: usize) -> usize {
+        assert!(row_bytes >= self.row_bytes);
+        assert!(count_lines == 0 || dst.len() >= (count_lines - 1) * row_bytes + self.row_bytes);
+        unsafe {
+            C_SkCodec_getScanlines(
+                self.codec.native_mut(),
+                dst.as_mut_ptr() as _,
+                count_lines.try_into().unwrap(),
+                row_bytes,
+            )
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[must_use]
+    pub fn skip_scanlines(&mut self, count_lines: usize) -> bool {
+        unsafe { C_SkCodec_skipScanlines(self.codec.native_mut(), count_lines.try_into().unwrap()) }
+    }
+
+    pub fn scanline_order(&self) -> ScanlineOrder {
+        ScanlineOrder::from_native(unsafe { C_SkCodec_getScanlineOrder(self.codec.native()) })
+    }
+
+    pub fn next_scanline(&self) -> usize {
+        unsafe { C_SkCodec_nextScanline(self.codec.native()) }
+            .try_into()
+            .unwrap()
+    }
+
+    pub fn output_scanline(&self, input_scanline: usize) -> usize {
+        unsafe { C_SkCodec_outputScanline(self.codec.native(), input_scanline.try_into().unwrap()) }
+            .try_into()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Codec, Result};
+    use crate::codec::codec_animation::{Blend, DisposalMethod};
+    use crate::{encode, Bitmap, Color, EncodedImageFormat, ImageInfo, Pixmap};
+
+    fn encoded_png() -> Vec<u8> {
+        let mut bitmap = Bitmap::new();
+        bitmap.alloc_n32_pixels((16, 8), None);
+        bitmap.erase_color(Color::RED);
+        encode::bitmap(&bitmap, EncodedImageFormat::PNG, 100)
+            .unwrap()
+            .as_bytes()
+            .to_vec()
+    }
+
+    #[test]
+    fn decode_all_at_once() {
+        let mut codec = Codec::from_bytes(&encoded_png()).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::PNG);
+        assert_eq!(codec.dimensions(), (16, 8).into());
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let result = codec.get_pixels(&info, &mut pixels, info.min_row_bytes(), None);
+        assert_eq!(result, Result::Success);
+        assert!(pixels.iter().any(|b| *b != 0));
+    }
+
+    #[test]
+    fn single_frame_info() {
+        let mut codec = Codec::from_bytes(&encoded_png()).unwrap();
+        assert_eq!(codec.frame_count(), 1);
+        assert_eq!(codec.repetition_count(), Some(0));
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut pixmap = Pixmap::new(&info, &mut pixels, info.min_row_bytes());
+        assert_eq!(
+            codec.get_frame_to_pixmap(&mut pixmap, 0, None),
+            Result::Success
+        );
+    }
+
+    #[test]
+    fn frame_indices_beyond_skia_are_rejected() {
+        let mut codec = Codec::from_bytes(&encoded_png()).unwrap();
+        assert!(codec.get_frame_info(1).is_none());
+        assert!(codec.get_frame_info(usize::max_value()).is_none());
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut pixmap = Pixmap::new(&info, &mut pixels, info.min_row_bytes());
+        assert_eq!(
+            codec.get_frame_to_pixmap(&mut pixmap, usize::max_value(), None),
+            Result::InvalidParameters
+        );
+    }
+
+    /// A 2x2 GIF that loops forever. The first frame fills the image and is kept for 100ms,
+    /// the second frame covers the bottom right pixel for 200ms and is then cleared.
+    #[rustfmt::skip]
+    const ANIMATED_GIF: &[u8] = &[
+        b'G', b'I', b'F', b'8', b'9', b'a', 2, 0, 2, 0, 0x91, 0, 0,
+        // global color table: black, red, green, blue
+        0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255,
+        // loop forever
+        0x21, 0xff, 11, b'N', b'E', b'T', b'S', b'C', b'A', b'P', b'E', b'2', b'.', b'0',
+        3, 1, 0, 0, 0,
+        // frame 0: keep, 10/100s, red
+        0x21, 0xf9, 4, 1 << 2, 10, 0, 0, 0,
+        0x2c, 0, 0, 0, 0, 2, 0, 2, 0, 0,
+        2, 3, 0x4c, 0x98, 0x14, 0,
+        // frame 1: restore background, 20/100s, green at (1, 1)
+        0x21, 0xf9, 4, 2 << 2, 20, 0, 0, 0,
+        0x2c, 1, 0, 1, 0, 1, 0, 1, 0, 0,
+        2, 2, 0x54, 0x01, 0,
+        0x3b,
+    ];
+
+    #[test]
+    fn animated_frame_infos() {
+        let mut codec = Codec::from_bytes(ANIMATED_GIF).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::GIF);
+        assert_eq!(codec.frame_count(), 2);
+        assert_eq!(codec.repetition_count(), None);
+
+        let frames = codec.frame_infos();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].duration, 100);
+        assert_eq!(frames[0].required_frame, None);
+        assert_eq!(frames[0].disposal_method, DisposalMethod::Keep);
+        assert_eq!(frames[0].blend, Blend::PriorFrame);
+        assert!(frames[0].fully_received);
+        assert_eq!(frames[1].duration, 200);
+        assert_eq!(frames[1].required_frame, Some(0));
+        assert_eq!(frames[1].disposal_method, DisposalMethod::RestoreBGColor);
+        assert_eq!(frames[1].blend, Blend::PriorFrame);
+        assert!(codec.get_frame_info(2).is_none());
+    }
+
+    #[test]
+    fn decode_animated_frame() {
+        let mut codec = Codec::from_bytes(ANIMATED_GIF).unwrap();
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut pixmap = Pixmap::new(&info, &mut pixels, info.min_row_bytes());
+        assert_eq!(
+            codec.get_frame_to_pixmap(&mut pixmap, 1, None),
+            Result::Success
+        );
+        assert_eq!(pixmap.get_color((0, 0)), Color::RED);
+        assert_eq!(pixmap.get_color((1, 1)), Color::GREEN);
+    }
+
+    #[test]
+    fn decode_incrementally() {
+        let png = encoded_png();
+        let (header, rest) = png.split_at(png.len() / 2);
+        let mut codec = Codec::new_incremental(header).unwrap();
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut decoder = codec
+            .start_incremental_decode(&info, &mut pixels, row_bytes, None)
+            .unwrap();
+        decoder.append_bytes(rest).mark_complete();
+        assert_eq!(decoder.decode().0, Result::Success);
+    }
+
+    #[test]
+    fn decode_scanlines() {
+        let mut codec = Codec::from_bytes(&encoded_png()).unwrap();
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let row_bytes = info.min_row_bytes();
+        let mut decoder = codec.start_scanline_decode(&info, None).unwrap();
+        let mut line = vec![0u8; row_bytes];
+        assert_eq!(decoder.get_scanlines(&mut line, 1, row_bytes), 1);
+        assert!(decoder.skip_scanlines(6));
+        assert_eq!(decoder.next_scanline(), 7);
+    }
+}
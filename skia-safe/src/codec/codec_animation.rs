@@ -0,0 +1,32 @@
+use crate::prelude::*;
+use skia_bindings::{SkCodecAnimation_Blend, SkCodecAnimation_DisposalMethod};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum DisposalMethod {
+    Keep = SkCodecAnimation_DisposalMethod::kKeep as _,
+    RestoreBGColor = SkCodecAnimation_DisposalMethod::kRestoreBGColor as _,
+    RestorePrevious = SkCodecAnimation_DisposalMethod::kRestorePrevious as _,
+}
+
+impl NativeTransmutable<SkCodecAnimation_DisposalMethod> for DisposalMethod {}
+#[test]
+fn test_disposal_method_layout() {
+    DisposalMethod::test_layout()
+}
+
+/// How a frame is combined with the prior frame.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum Blend {
+    /// The frame is drawn on top of the prior frame.
+    PriorFrame = SkCodecAnimation_Blend::kPriorFrame as _,
+    /// The frame replaces the prior frame in its rectangle.
+    BG = SkCodecAnimation_Blend::kBG as _,
+}
+
+impl NativeTransmutable<SkCodecAnimation_Blend> for Blend {}
+#[test]
+fn test_blend_layout() {
+    Blend::test_layout()
+}
@@ -1,9 +1,10 @@
 use crate::prelude::*;
 use crate::{gpu, Drawable, Pixmap};
 use crate::{
-    scalar, vertices, Bitmap, BlendMode, ClipOp, Color, Data, Font, IPoint, IRect, ISize, Image,
-    ImageFilter, ImageInfo, Matrix, Paint, Path, Picture, Point, QuickReject, RRect, Rect, Region,
-    Surface, SurfaceProps, TextBlob, TextEncoding, Vector, Vertices,
+    scalar, vertices, Bitmap, BlendMode, ClipOp, Color, Data, Font, GlyphId, IPoint, IRect, ISize,
+    Image, ImageFilter, ImageInfo, Matrix, Paint, Path, Picture, Point, QuickReject, RRect,
+    RSXform, Rect, Region, Surface, SurfaceProps, TextBlob, TextBlobBuilder, TextEncoding, Vector,
+    Vertices,
 };
 use skia_bindings::{
     C_SkAutoCanvasRestore_Construct, C_SkAutoCanvasRestore_destruct, C_SkAutoCanvasRestore_restore,
@@ -19,7 +20,7 @@ use std::convert::TryInto;
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
-use std::{mem, slice};
+use std::{mem, ptr, slice};
 
 pub use lattice::Lattice;
 
@@ -165,7 +166,12 @@ impl NativeAccess<SkCanvas> for Canvas {
 /// This is the type representing a canvas that is owned and dropped
 /// when it goes out of scope _and_ is bound to a the lifetime of another
 /// instance. Function resolvement is done via the Deref trait.
-pub struct OwnedCanvas<'lt>(*mut Canvas, PhantomData<&'lt ()>);
+pub struct OwnedCanvas<'lt>(
+    *mut Canvas,
+    PhantomData<&'lt ()>,
+    // Called after the native canvas is deleted.
+    Option<Box<dyn FnOnce() + 'lt>>,
+);
 
 impl<'lt> Deref for OwnedCanvas<'lt> {
     type Target = Canvas;
@@ -184,6 +190,17 @@ impl<'lt> DerefMut for OwnedCanvas<'lt> {
 impl<'lt> Drop for OwnedCanvas<'lt> {
     fn drop(&mut self) {
         unsafe { C_SkCanvas_delete(self.native()) }
+        if let Some(deleted) = self.2.take() {
+            deleted()
+        }
+    }
+}
+
+impl<'lt> OwnedCanvas<'lt> {
+    // Sets the function that is called after the native canvas is deleted.
+    pub(crate) fn on_deleted(mut self, deleted: impl FnOnce() + 'lt) -> Self {
+        self.2 = Some(Box::new(deleted));
+        self
     }
 }
 
@@ -931,6 +948,62 @@ impl Canvas {
         self
     }
 
+    /// Draws glyphs at their positions relative to `origin`.
+    ///
+    /// `glyphs` and `positions` must have the same length.
+    pub fn draw_glyphs(
+        &mut self,
+        glyphs: &[GlyphId],
+        positions: &[Point],
+        origin: impl Into<Point>,
+        font: &Font,
+        paint: &Paint,
+    ) -> &mut Self {
+        assert_eq!(glyphs.len(), positions.len());
+        if glyphs.is_empty() {
+            return self;
+        }
+        let mut builder = TextBlobBuilder::new();
+        {
+            let (run_glyphs, run_positions) =
+                builder.alloc_run_pos(font.clone(), glyphs.len(), None);
+            run_glyphs.copy_from_slice(glyphs);
+            run_positions.copy_from_slice(positions);
+        }
+        if let Some(blob) = builder.make() {
+            self.draw_text_blob(&blob, origin, paint);
+        }
+        self
+    }
+
+    /// Draws glyphs, each one transformed by its own rotation, scale, and translation
+    /// relative to `origin`.
+    ///
+    /// `glyphs` and `xforms` must have the same length.
+    pub fn draw_glyphs_rsxform(
+        &mut self,
+        glyphs: &[GlyphId],
+        xforms: &[RSXform],
+        origin: impl Into<Point>,
+        font: &Font,
+        paint: &Paint,
+    ) -> &mut Self {
+        assert_eq!(glyphs.len(), xforms.len());
+        if glyphs.is_empty() {
+            return self;
+        }
+        let mut builder = TextBlobBuilder::new();
+        {
+            let (run_glyphs, run_xforms) = builder.alloc_run_rsxform(font, glyphs.len());
+            run_glyphs.copy_from_slice(glyphs);
+            run_xforms.copy_from_slice(xforms);
+        }
+        if let Some(blob) = builder.make() {
+            self.draw_text_blob(&blob, origin, paint);
+        }
+        self
+    }
+
     pub fn draw_text_blob(&mut self, blob: &TextBlob, origin: impl Into<Point>, paint: &Paint) {
         let origin = origin.into();
         unsafe {
@@ -1003,7 +1076,42 @@ impl Canvas {
         self
     }
 
-    // TODO: drawAtlas
+    /// Draws sprites from `atlas`, each sprite is the part `tex[i]` of the atlas, transformed
+    /// by `xform[i]`. If `colors` are given, each sprite is blended with its color using `mode`.
+    ///
+    /// `xform`, `tex`, and `colors` must have the same length.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_atlas(
+        &mut self,
+        atlas: &Image,
+        xform: &[RSXform],
+        tex: &[Rect],
+        colors: Option<&[Color]>,
+        mode: BlendMode,
+        cull_rect: Option<&Rect>,
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        let count = xform.len();
+        assert_eq!(count, tex.len());
+        if let Some(colors) = colors {
+            assert_eq!(count, colors.len());
+        }
+        unsafe {
+            self.native_mut().drawAtlas(
+                atlas.native(),
+                xform.native().as_ptr(),
+                tex.native().as_ptr(),
+                colors
+                    .map(|colors| colors.native().as_ptr())
+                    .unwrap_or(ptr::null()),
+                count.try_into().unwrap(),
+                mode.into_native(),
+                cull_rect.native_ptr_or_null(),
+                paint.native_ptr_or_null(),
+            )
+        }
+        self
+    }
 
     pub fn draw_drawable(&mut self, drawable: &mut Drawable, matrix: Option<&Matrix>) {
         unsafe {
@@ -1058,6 +1166,7 @@ impl Canvas {
             Some(OwnedCanvas::<'lt>(
                 Self::borrow_from_native(unsafe { &mut *native }),
                 PhantomData,
+                None,
             ))
         } else {
             None
@@ -1298,6 +1407,128 @@ mod tests {
         canvas.clear(Color::RED);
     }
 
+    #[test]
+    fn draw_atlas_draws_like_individual_image_rects() {
+        use crate::{BlendMode, Paint, RSXform, SrcRectConstraint, Surface};
+
+        // an atlas with a red and a blue sprite.
+        let mut atlas = Surface::new_raster_n32_premul((4, 2)).unwrap();
+        atlas.canvas().clear(Color::RED);
+        atlas.canvas().draw_rect(
+            Rect::new(2.0, 0.0, 4.0, 2.0),
+            Paint::default().set_color(Color::BLUE),
+        );
+        let atlas = atlas.image_snapshot();
+
+        let tex = [Rect::new(0.0, 0.0, 2.0, 2.0), Rect::new(2.0, 0.0, 4.0, 2.0)];
+        let xform = [
+            RSXform::new(1.0, 0.0, (1, 1)),
+            RSXform::new(1.0, 0.0, (5, 4)),
+        ];
+
+        let mut atlas_pixels = [0u32; 64];
+        {
+            let mut canvas =
+                Canvas::from_raster_direct_n32((8, 8), atlas_pixels.as_mut(), None).unwrap();
+            canvas.draw_atlas(&atlas, &xform, &tex, None, BlendMode::SrcOver, None, None);
+        }
+
+        let mut rect_pixels = [0u32; 64];
+        {
+            let mut canvas =
+                Canvas::from_raster_direct_n32((8, 8), rect_pixels.as_mut(), None).unwrap();
+            for (xform, tex) in xform.iter().zip(tex.iter()) {
+                let dst = Rect::from_xywh(xform.tx, xform.ty, tex.width(), tex.height());
+                canvas.draw_image_rect(
+                    &atlas,
+                    Some((tex, SrcRectConstraint::Strict)),
+                    dst,
+                    &Paint::default(),
+                );
+            }
+        }
+
+        assert!(atlas_pixels.iter().any(|&p| p != 0));
+        assert_eq!(atlas_pixels.as_ref(), rect_pixels.as_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn draw_atlas_panics_on_mismatching_lengths() {
+        use crate::{BlendMode, RSXform, Surface};
+
+        let atlas = Surface::new_raster_n32_premul((2, 2))
+            .unwrap()
+            .image_snapshot();
+        let mut canvas = OwnedCanvas::default();
+        canvas.draw_atlas(
+            &atlas,
+            &[RSXform::new(1.0, 0.0, (0, 0))],
+            &[],
+            None,
+            BlendMode::SrcOver,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn draw_glyphs_draws_like_draw_str() {
+        use crate::{Font, Paint, Point, RSXform};
+
+        let font = Font::default();
+        let paint = Paint::default();
+        let glyphs = font.str_to_glyphs_vec("Hi");
+        let mut widths = vec![0.0; glyphs.len()];
+        font.get_widths(&glyphs, &mut widths);
+        let positions: Vec<Point> = widths
+            .iter()
+            .scan(0.0, |x, width| {
+                let position = Point::new(*x, 0.0);
+                *x += width;
+                Some(position)
+            })
+            .collect();
+
+        let mut str_pixels = [0u32; 32 * 32];
+        {
+            let mut canvas =
+                Canvas::from_raster_direct_n32((32, 32), str_pixels.as_mut(), None).unwrap();
+            canvas.draw_str("Hi", (4, 20), &font, &paint);
+        }
+
+        let mut glyph_pixels = [0u32; 32 * 32];
+        {
+            let mut canvas =
+                Canvas::from_raster_direct_n32((32, 32), glyph_pixels.as_mut(), None).unwrap();
+            canvas.draw_glyphs(&glyphs, &positions, (4, 20), &font, &paint);
+        }
+
+        let mut xform_pixels = [0u32; 32 * 32];
+        {
+            let xforms: Vec<RSXform> = positions
+                .iter()
+                .map(|p| RSXform::new(1.0, 0.0, *p))
+                .collect();
+            let mut canvas =
+                Canvas::from_raster_direct_n32((32, 32), xform_pixels.as_mut(), None).unwrap();
+            canvas.draw_glyphs_rsxform(&glyphs, &xforms, (4, 20), &font, &paint);
+        }
+
+        assert!(str_pixels.iter().any(|&p| p != 0));
+        assert_eq!(str_pixels.as_ref(), glyph_pixels.as_ref());
+        assert_eq!(str_pixels.as_ref(), xform_pixels.as_ref());
+    }
+
+    #[test]
+    fn draw_no_glyphs() {
+        use crate::{Font, Paint};
+
+        let mut canvas = OwnedCanvas::default();
+        canvas.draw_glyphs(&[], &[], (0, 0), &Font::default(), &Paint::default());
+        canvas.draw_glyphs_rsxform(&[], &[], (0, 0), &Font::default(), &Paint::default());
+    }
+
     #[test]
     fn clip_options_overloads() {
         let mut c = OwnedCanvas::default();
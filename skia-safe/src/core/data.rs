@@ -1,11 +1,13 @@
+use crate::interop::RustStream;
 use crate::prelude::*;
 use skia_bindings::{
-    C_SkData_MakeEmpty, C_SkData_MakeSubset, C_SkData_MakeUninitialized, C_SkData_MakeWithCString,
-    C_SkData_MakeWithCopy, C_SkData_ref, C_SkData_unique, C_SkData_unref, SkData,
+    C_SkData_MakeEmpty, C_SkData_MakeFromStream, C_SkData_MakeFromStreamToEnd, C_SkData_MakeSubset,
+    C_SkData_MakeUninitialized, C_SkData_MakeWithCString, C_SkData_MakeWithCopy, C_SkData_ref,
+    C_SkData_unique, C_SkData_unref, SkData,
 };
 use std::ffi::CStr;
 use std::ops::Deref;
-use std::slice;
+use std::{io, slice};
 
 pub type Data = RCHandle<SkData>;
 unsafe impl Send for Data {}
@@ -89,7 +91,32 @@ impl RCHandle<SkData> {
 
     // TODO: MakeFromFileName (not sure if we need that)
     // TODO: MakeFromFile (not sure if we need that)
-    // TODO: MakeFromStream
+
+    /// Reads exactly size bytes from the stream, or all its bytes if size is `None`.
+    pub fn from_stream(stream: impl io::Read, size: impl Into<Option<usize>>) -> io::Result<Data> {
+        Self::from_rust_stream(&mut RustStream::new(stream), size.into())
+    }
+
+    /// Reads all the remaining bytes from the stream.
+    pub fn from_seekable_stream(stream: impl io::Read + io::Seek) -> io::Result<Data> {
+        let mut stream = RustStream::new_seekable(stream)?;
+        let size = stream.length().unwrap();
+        Self::from_rust_stream(&mut stream, Some(size))
+    }
+
+    fn from_rust_stream(stream: &mut RustStream, size: Option<usize>) -> io::Result<Data> {
+        let data = Data::from_ptr(unsafe {
+            match size {
+                Some(size) => C_SkData_MakeFromStream(stream.stream_mut(), size),
+                None => C_SkData_MakeFromStreamToEnd(stream.stream_mut()),
+            }
+        });
+        match (stream.take_error(), data) {
+            (Some(error), _) => Err(error),
+            (None, Some(data)) => Ok(data),
+            (None, None) => Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
 
     pub fn new_empty() -> Self {
         Data::from_ptr(unsafe { C_SkData_MakeEmpty() }).unwrap()
@@ -103,3 +130,38 @@ fn data_supports_equals() {
     let d2 = Data::new_copy(x);
     assert!(d1 == d2)
 }
+
+#[test]
+fn data_from_stream() {
+    let bytes = [1u8, 2, 3, 4, 5];
+    let data = Data::from_stream(&bytes[..], 4).unwrap();
+    assert_eq!(data.as_bytes(), &bytes[..4]);
+    assert!(Data::from_stream(&bytes[..], 6).is_err());
+    let data = Data::from_stream(&bytes[..], None).unwrap();
+    assert_eq!(data.as_bytes(), &bytes[..]);
+}
+
+#[test]
+fn data_from_stream_reports_reader_errors() {
+    struct FailingReader;
+
+    impl io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+    }
+
+    assert_eq!(
+        Data::from_stream(FailingReader, None).unwrap_err().kind(),
+        io::ErrorKind::ConnectionReset
+    );
+}
+
+#[test]
+fn data_from_seekable_stream() {
+    use std::io::{Cursor, Seek, SeekFrom};
+    let mut cursor = Cursor::new(vec![1u8, 2, 3, 4, 5]);
+    cursor.seek(SeekFrom::Start(2)).unwrap();
+    let data = Data::from_seekable_stream(cursor).unwrap();
+    assert_eq!(data.as_bytes(), &[3, 4, 5]);
+}
@@ -1,14 +1,13 @@
-use crate::interop::DynamicMemoryWStream;
 use crate::prelude::*;
 use crate::{Canvas, Data, Rect, Size};
 use skia_bindings::{SkDocument, SkRefCntBase};
-use std::pin::Pin;
+use std::io;
 
-pub struct Document<State = state::Open> {
+pub struct Document<State = state::Open, Sink = sink::Memory> {
     // note: order matters here, first the document must be
     // dropped _and then_ the stream.
     document: RCHandle<SkDocument>,
-    stream: Pin<Box<DynamicMemoryWStream>>,
+    sink: Sink,
 
     state: State,
 }
@@ -36,21 +35,70 @@ pub mod state {
     }
 }
 
-impl<S> Document<S> {
+pub mod sink {
+    use crate::interop::{DynamicMemoryWStream, RustWStream};
+    use skia_bindings::SkWStream;
+    use std::io;
+    use std::pin::Pin;
+
+    /// The document is written to memory and returned when it's closed.
+    pub struct Memory(Pin<Box<DynamicMemoryWStream>>);
+
+    /// The document is written to a writer while its pages are finished.
+    pub struct Writer<'a>(RustWStream<'a>);
+
+    impl Memory {
+        pub(crate) fn new() -> Self {
+            // we can't move the memory stream around anymore as soon it's referred by
+            // the document.
+            Memory(Box::pin(DynamicMemoryWStream::new()))
+        }
+
+        pub(crate) fn stream_mut(&mut self) -> &mut SkWStream {
+            &mut self.0.native_mut()._base
+        }
+
+        pub(crate) fn detach_as_data(&mut self) -> crate::Data {
+            self.0.detach_as_data()
+        }
+    }
+
+    impl<'a> Writer<'a> {
+        pub(crate) fn new(writer: impl io::Write + 'a) -> Self {
+            Writer(RustWStream::new(writer))
+        }
+
+        pub(crate) fn stream_mut(&mut self) -> &mut SkWStream {
+            self.0.stream_mut()
+        }
+
+        /// Returns the first error that happened while writing.
+        pub(crate) fn check_error(&mut self) -> io::Result<()> {
+            match self.0.take_error() {
+                Some(error) => Err(error),
+                None => Ok(()),
+            }
+        }
+
+        pub(crate) fn flush(&mut self) -> io::Result<()> {
+            self.0.flush();
+            self.check_error()
+        }
+    }
+}
+
+impl<S, Sink> Document<S, Sink> {
     pub fn abort(mut self) {
         unsafe { self.document.native_mut().abort() }
         drop(self)
     }
 }
 
-impl Document {
-    pub(crate) fn new(
-        stream: Pin<Box<DynamicMemoryWStream>>,
-        document: RCHandle<SkDocument>,
-    ) -> Self {
+impl<Sink> Document<state::Open, Sink> {
+    pub(crate) fn new(sink: Sink, document: RCHandle<SkDocument>) -> Self {
         Document {
             document,
-            stream,
+            sink,
             state: state::Open { pages: 0 },
         }
     }
@@ -66,7 +114,7 @@ impl Document {
         mut self,
         size: impl Into<Size>,
         content: Option<&Rect>,
-    ) -> Document<state::OnPage> {
+    ) -> Document<state::OnPage, Sink> {
         let size = size.into();
         let canvas = unsafe {
             self.document.native_mut().beginPage(
@@ -77,7 +125,7 @@ impl Document {
         };
 
         Document {
-            stream: self.stream,
+            sink: self.sink,
             document: self.document,
             state: state::OnPage {
                 canvas,
@@ -85,18 +133,31 @@ impl Document {
             },
         } as _
     }
+}
 
+impl Document {
     /// Close the document and return the encoded representation.
     /// This function consumes and drops the document.
     pub fn close(mut self) -> Data {
         unsafe {
             self.document.native_mut().close();
         };
-        self.stream.detach_as_data()
+        self.sink.detach_as_data()
     }
 }
 
-impl Document<state::OnPage> {
+impl<'a> Document<state::Open, sink::Writer<'a>> {
+    /// Close the document, write its remaining parts, and flush the writer.
+    /// This function consumes and drops the document.
+    pub fn close(mut self) -> io::Result<()> {
+        unsafe {
+            self.document.native_mut().close();
+        };
+        self.sink.flush()
+    }
+}
+
+impl<Sink> Document<state::OnPage, Sink> {
     /// The current page we are currently drawing on.
     pub fn page(&self) -> usize {
         self.state.page
@@ -107,16 +168,13 @@ impl Document<state::OnPage> {
         Canvas::borrow_from_native(unsafe { &mut *self.state.canvas })
     }
 
-    /// Ends the page.
-    /// This function consumes the document and returns a new open document that
-    /// contains the pages drawn so far.
-    pub fn end_page(mut self) -> Document {
+    fn end_page_unchecked(mut self) -> Document<state::Open, Sink> {
         unsafe {
             self.document.native_mut().endPage();
         }
 
         Document {
-            stream: self.stream,
+            sink: self.sink,
             document: self.document,
             state: state::Open {
                 pages: self.state.page,
@@ -129,3 +187,24 @@ impl Document<state::OnPage> {
         //       current page.
     }
 }
+
+impl Document<state::OnPage> {
+    /// Ends the page.
+    /// This function consumes the document and returns a new open document that
+    /// contains the pages drawn so far.
+    pub fn end_page(self) -> Document {
+        self.end_page_unchecked()
+    }
+}
+
+impl<'a> Document<state::OnPage, sink::Writer<'a>> {
+    /// Ends the page and returns the first error that happened while writing the
+    /// document so far.
+    /// This function consumes the document and returns a new open document that
+    /// contains the pages drawn so far.
+    pub fn end_page(self) -> io::Result<Document<state::Open, sink::Writer<'a>>> {
+        let mut document = self.end_page_unchecked();
+        document.sink.check_error()?;
+        Ok(document)
+    }
+}
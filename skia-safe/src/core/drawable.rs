@@ -1,10 +1,13 @@
 use crate::prelude::*;
-use crate::{gpu, Canvas, IRect, ImageInfo, Matrix, NativeFlattenable, Point, Rect};
+use crate::{gpu, Canvas, IRect, ImageInfo, Matrix, NativeFlattenable, Picture, Point, Rect};
 use skia_bindings::{
-    C_SkDrawable_Deserialize, C_SkDrawable_GpuDrawHandler_destruct,
-    C_SkDrawable_GpuDrawHandler_draw, C_SkDrawable_snapGpuDrawHandler, SkDrawable,
-    SkDrawable_GpuDrawHandler, SkFlattenable, SkRefCntBase,
+    C_RustDrawable_new, C_SkDrawable_Deserialize, C_SkDrawable_GpuDrawHandler_destruct,
+    C_SkDrawable_GpuDrawHandler_draw, C_SkDrawable_newPictureSnapshot,
+    C_SkDrawable_snapGpuDrawHandler, SkCanvas, SkDrawable, SkDrawable_GpuDrawHandler,
+    SkFlattenable, SkPicture, SkRect, SkRefCntBase,
 };
+use std::ffi::c_void;
+use std::ptr;
 
 pub type Drawable = RCHandle<SkDrawable>;
 
@@ -27,6 +30,27 @@ impl NativeFlattenable for SkDrawable {
 }
 
 impl RCHandle<SkDrawable> {
+    /// Creates a drawable that draws with `drawable`.
+    ///
+    /// A drawable that is drawn into a `PictureRecorder` is called each time the drawable
+    /// returned by `finish_recording_as_drawable()` is drawn, while pictures returned by
+    /// `finish_recording_as_picture()` contain a snapshot of it. When the drawing changes,
+    /// call `notify_drawing_changed()`.
+    pub fn new(drawable: impl DrawableImpl + 'static) -> Drawable {
+        // a thin pointer to pass through the C++ drawable.
+        let drawable: Box<Box<dyn DrawableImpl>> = Box::new(Box::new(drawable));
+        Drawable::from_ptr(unsafe {
+            C_RustDrawable_new(
+                Box::into_raw(drawable) as *mut c_void,
+                Some(draw),
+                Some(get_bounds),
+                Some(new_picture_snapshot),
+                Some(drop_drawable),
+            )
+        })
+        .unwrap()
+    }
+
     pub fn draw(&mut self, canvas: &mut Canvas, matrix: Option<&Matrix>) {
         unsafe {
             self.native_mut()
@@ -62,12 +86,9 @@ impl RCHandle<SkDrawable> {
         .map(GPUDrawHandler)
     }
 
-    // TODO: clarify ref-counter situation here, return value is SkPicture*
-    /*
     pub fn new_picture_snapshot(&mut self) -> Option<Picture> {
-        unimplemented!()
+        Picture::from_ptr(unsafe { C_SkDrawable_newPictureSnapshot(self.native_mut()) })
     }
-    */
 
     pub fn generation_id(&mut self) -> u32 {
         unsafe { self.native_mut().getGenerationID() }
@@ -82,6 +103,54 @@ impl RCHandle<SkDrawable> {
     }
 }
 
+/// A drawable implemented in Rust, see `Drawable::new()`.
+///
+/// Skia calls a drawable whenever it is drawn, for example when a picture that contains it is
+/// played back, so there is no Rust caller a panic could be resumed in. Panics are caught
+/// instead and do not propagate: if `on_draw()` panics, the drawable draws nothing, if
+/// `on_bounds()` panics, its bounds are empty, and if `on_snapshot()` panics, the default
+/// snapshot is used.
+pub trait DrawableImpl {
+    fn on_draw(&mut self, canvas: &mut Canvas);
+
+    /// Returns the conservative bounds of what the drawable draws.
+    fn on_bounds(&mut self) -> Rect;
+
+    /// Returns a picture of the current drawing. By default, `on_draw()` is recorded into
+    /// a picture.
+    fn on_snapshot(&mut self) -> Option<Picture> {
+        None
+    }
+}
+
+unsafe fn drawable_impl<'a>(drawable: *mut c_void) -> &'a mut dyn DrawableImpl {
+    &mut **(drawable as *mut Box<dyn DrawableImpl>)
+}
+
+// Panics of the drawable must not unwind into Skia, see DrawableImpl.
+
+unsafe extern "C" fn draw(drawable: *mut c_void, canvas: *mut SkCanvas) {
+    catch_panic_or((), || {
+        drawable_impl(drawable).on_draw(Canvas::borrow_from_native(&mut *canvas))
+    })
+}
+
+unsafe extern "C" fn get_bounds(drawable: *mut c_void, bounds: *mut SkRect) {
+    *bounds = catch_panic_or(Rect::default(), || drawable_impl(drawable).on_bounds()).into_native()
+}
+
+unsafe extern "C" fn new_picture_snapshot(drawable: *mut c_void) -> *mut SkPicture {
+    catch_panic_or(ptr::null_mut(), || {
+        drawable_impl(drawable).on_snapshot().shared_ptr() as _
+    })
+}
+
+unsafe extern "C" fn drop_drawable(drawable: *mut c_void) {
+    catch_panic_or((), || {
+        drop(Box::from_raw(drawable as *mut Box<dyn DrawableImpl>))
+    })
+}
+
 pub struct GPUDrawHandler(*mut SkDrawable_GpuDrawHandler);
 
 impl NativeAccess<SkDrawable_GpuDrawHandler> for GPUDrawHandler {
@@ -107,3 +176,104 @@ impl GPUDrawHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Paint, PictureRecorder};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Square {
+        color: Rc<Cell<Color>>,
+    }
+
+    impl DrawableImpl for Square {
+        fn on_draw(&mut self, canvas: &mut Canvas) {
+            canvas.draw_rect(
+                self.on_bounds(),
+                Paint::default().set_color(self.color.get()),
+            );
+        }
+
+        fn on_bounds(&mut self) -> Rect {
+            Rect::new(0.0, 0.0, 2.0, 2.0)
+        }
+    }
+
+    fn play_back(recording: &mut Drawable) -> u32 {
+        let mut pixels = [0u32; 4];
+        {
+            let mut canvas = Canvas::from_raster_direct_n32((2, 2), pixels.as_mut(), None).unwrap();
+            recording.draw(&mut canvas, None);
+        }
+        pixels[0]
+    }
+
+    #[test]
+    fn drawable_is_evaluated_on_each_playback() {
+        let color = Rc::new(Cell::new(Color::RED));
+        let mut drawable = Drawable::new(Square {
+            color: color.clone(),
+        });
+        assert_eq!(drawable.bounds(), Rect::new(0.0, 0.0, 2.0, 2.0));
+
+        let mut recorder = PictureRecorder::new();
+        recorder
+            .begin_recording(Rect::new(0.0, 0.0, 2.0, 2.0), None, None)
+            .draw_drawable(&mut drawable, None);
+        let mut recording = recorder.finish_recording_as_drawable().unwrap();
+
+        let red = play_back(&mut recording);
+        let generation_id = drawable.generation_id();
+        color.set(Color::BLUE);
+        drawable.notify_drawing_changed();
+        assert_ne!(drawable.generation_id(), generation_id);
+        let blue = play_back(&mut recording);
+        assert_ne!(red, blue);
+    }
+
+    #[test]
+    fn default_snapshot_records_the_drawing() {
+        let mut drawable = Drawable::new(Square {
+            color: Rc::new(Cell::new(Color::RED)),
+        });
+        let snapshot = drawable.new_picture_snapshot().unwrap();
+        assert_eq!(snapshot.cull_rect(), Rect::new(0.0, 0.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn drawable_impl_is_dropped_with_the_drawable() {
+        let color = Rc::new(Cell::new(Color::RED));
+        let drawable = Drawable::new(Square {
+            color: color.clone(),
+        });
+        assert_eq!(Rc::strong_count(&color), 2);
+        drop(drawable);
+        assert_eq!(Rc::strong_count(&color), 1);
+    }
+
+    #[test]
+    fn panics_of_the_drawable_are_not_unwound_into_skia() {
+        struct Panicking;
+
+        impl DrawableImpl for Panicking {
+            fn on_draw(&mut self, _canvas: &mut Canvas) {
+                panic!("on_draw")
+            }
+
+            fn on_bounds(&mut self) -> Rect {
+                panic!("on_bounds")
+            }
+        }
+
+        let mut drawable = Drawable::new(Panicking);
+        assert!(drawable.bounds().is_empty());
+        let mut recorder = PictureRecorder::new();
+        recorder
+            .begin_recording(Rect::new(0.0, 0.0, 2.0, 2.0), None, None)
+            .draw_drawable(&mut drawable, None);
+        let mut recording = recorder.finish_recording_as_drawable().unwrap();
+        play_back(&mut recording);
+    }
+}
@@ -1,14 +1,18 @@
 use crate::prelude::*;
 use crate::{
-    scalar, FontHinting, FontMetrics, GlyphId, Paint, Path, Point, Rect, TextEncoding, Typeface,
-    Unichar,
+    scalar, FontHinting, FontMetrics, GlyphId, Matrix, Paint, Path, Point, Rect, TextEncoding,
+    Typeface, Unichar,
 };
 use skia_bindings::{
     C_SkFont_ConstructFromTypeface, C_SkFont_ConstructFromTypefaceWithSize,
-    C_SkFont_ConstructFromTypefaceWithSizeScaleAndSkew, C_SkFont_Equals, C_SkFont_destruct,
-    C_SkFont_makeWithSize, C_SkFont_setTypeface, SkFont, SkFont_Edging,
+    C_SkFont_ConstructFromTypefaceWithSizeScaleAndSkew, C_SkFont_CopyConstruct, C_SkFont_Equals,
+    C_SkFont_destruct, C_SkFont_makeWithSize, C_SkFont_setTypeface, SkFont, SkFont_Edging,
+    SkMatrix, SkPath,
 };
-use std::{mem, ptr};
+use std::any::Any;
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::{mem, ptr, slice};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[repr(i32)]
@@ -32,6 +36,16 @@ impl NativeDrop for SkFont {
     }
 }
 
+impl NativeClone for SkFont {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut font = mem::zeroed();
+            C_SkFont_CopyConstruct(&mut font, self);
+            font
+        }
+    }
+}
+
 impl NativePartialEq for SkFont {
     fn eq(&self, rhs: &Self) -> bool {
         unsafe { C_SkFont_Equals(self, rhs) }
@@ -428,7 +442,54 @@ impl Handle<SkFont> {
         unsafe { self.native().getPath(glyph_id, path.native_mut()) }.if_true_some(path)
     }
 
-    // TODO: getPaths() (needs a function to be passed, but supports a context).
+    /// Calls `path_proc` for each glyph in `glyphs` in order with the glyph's path, or `None`
+    /// if the glyph has no path, and the matrix that maps the path to the font's size.
+    pub fn get_paths(
+        &self,
+        glyphs: &[GlyphId],
+        mut path_proc: impl FnMut(GlyphId, Option<&Path>, &Matrix),
+    ) {
+        struct Context<'a> {
+            glyphs: slice::Iter<'a, GlyphId>,
+            path_proc: &'a mut dyn FnMut(GlyphId, Option<&Path>, &Matrix),
+            // panics must not unwind into Skia, so they are resumed after getPaths() returns.
+            panic: Option<Box<dyn Any + Send>>,
+        }
+
+        unsafe extern "C" fn glyph_path_proc(
+            path: *const SkPath,
+            matrix: *const SkMatrix,
+            context: *mut c_void,
+        ) {
+            let context = &mut *(context as *mut Context);
+            if context.panic.is_some() {
+                return;
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let glyph_id = *context.glyphs.next().unwrap();
+                let path = path.as_ref().map(|path| Path::from_native_ref(path));
+                (context.path_proc)(glyph_id, path, Matrix::from_native_ref(&*matrix))
+            }));
+            context.panic = result.err();
+        }
+
+        let mut context = Context {
+            glyphs: glyphs.iter(),
+            path_proc: &mut path_proc,
+            panic: None,
+        };
+        unsafe {
+            self.native().getPaths(
+                glyphs.as_ptr(),
+                glyphs.len().try_into().unwrap(),
+                Some(glyph_path_proc),
+                &mut context as *mut Context as *mut c_void,
+            )
+        }
+        if let Some(panic) = context.panic {
+            panic::resume_unwind(panic)
+        }
+    }
 
     pub fn metrics(&self) -> (scalar, FontMetrics) {
         let mut fm = unsafe { mem::zeroed() };
@@ -440,3 +501,39 @@ impl Handle<SkFont> {
         unsafe { self.native().getMetrics(ptr::null_mut()) }
     }
 }
+
+#[test]
+fn get_paths_matches_get_path() {
+    let font = Font::default();
+    let glyphs = font.str_to_glyphs_vec("A B");
+    let mut visited = Vec::new();
+    font.get_paths(&glyphs, |glyph_id, path, matrix| {
+        let expected = font.get_path(glyph_id);
+        match path {
+            Some(path) => {
+                let mut path = path.clone();
+                path.transform(matrix);
+                assert_eq!(Some(path.bounds()), expected.map(|p| p.bounds()));
+            }
+            None => assert!(expected.is_none()),
+        }
+        visited.push(glyph_id);
+    });
+    assert_eq!(visited, glyphs);
+}
+
+#[test]
+#[should_panic(expected = "path_proc")]
+fn get_paths_resumes_panics_of_path_proc() {
+    let font = Font::default();
+    let glyphs = font.str_to_glyphs_vec("AB");
+    font.get_paths(&glyphs, |_, _, _| panic!("path_proc"));
+}
+
+#[test]
+fn cloned_font_equals_the_original() {
+    let mut font = Font::default();
+    font.set_size(20.0);
+    let clone = font.clone();
+    assert_eq!(clone, font);
+}
@@ -1,15 +1,22 @@
 use crate::interop;
-use crate::interop::DynamicMemoryWStream;
+use crate::interop::{DynamicMemoryWStream, RustStream};
 use crate::prelude::*;
-use crate::{FontStyle, Typeface, Unichar};
+use crate::{Data, FontStyle, Typeface, Unichar};
 use skia_bindings::{
-    C_SkFontMgr_RefDefault, C_SkFontMgr_makeFromStream, C_SkFontStyleSet_count,
+    C_RustFontMgr_new, C_SkFontMgr_RefDefault, C_SkFontMgr_legacyMakeTypeface,
+    C_SkFontMgr_makeFromData, C_SkFontMgr_makeFromStream, C_SkFontStyleSet_count,
     C_SkFontStyleSet_createTypeface, C_SkFontStyleSet_getStyle, C_SkFontStyleSet_matchStyle,
-    SkFontMgr, SkFontStyleSet, SkRefCntBase,
+    SkFontMgr, SkFontStyle, SkFontStyleSet, SkRefCntBase, SkString, SkTypeface,
 };
-use std::ffi::CString;
-use std::mem;
-use std::os::raw::c_char;
+#[cfg(target_os = "linux")]
+use skia_bindings::{
+    C_SkFontMgr_New_Custom_Directory, C_SkFontMgr_New_Custom_Embedded, C_SkFontMgr_New_Custom_Empty,
+};
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::{c_char, c_int};
+#[cfg(target_os = "linux")]
+use std::path::Path;
+use std::{io, mem, ptr, slice};
 
 pub type FontStyleSet = RCHandle<SkFontStyleSet>;
 
@@ -88,6 +95,53 @@ impl Default for RCHandle<SkFontMgr> {
     }
 }
 
+/// Custom font managers that only provide the fonts they are given, instead of the fonts
+/// installed on the system.
+///
+/// A typeface created from such a font manager, for example with `match_family_style()`
+/// or `legacy_make_typeface()`, can then be used to create a `Font` for the text APIs.
+///
+/// Only available on Linux, the only platform the bindings compile Skia's custom font
+/// managers for.
+#[cfg(target_os = "linux")]
+impl RCHandle<SkFontMgr> {
+    /// Creates a font manager that provides the fonts found in the directory `dir`.
+    ///
+    /// Returns `None` if the path is not valid UTF-8.
+    ///
+    /// Only available on Linux.
+    pub fn new_custom_directory(dir: impl AsRef<Path>) -> Option<FontMgr> {
+        let dir = CString::new(dir.as_ref().to_str()?).ok()?;
+        FontMgr::from_ptr(unsafe { C_SkFontMgr_New_Custom_Directory(dir.as_ptr()) })
+    }
+
+    /// Creates a font manager that provides the fonts of the font files in `fonts`, which
+    /// are usually included in the binary with `include_bytes!()`.
+    ///
+    /// The font data is not copied, which is why it must be `'static`.
+    ///
+    /// Only available on Linux.
+    pub fn new_custom_embedded(fonts: &[&'static [u8]]) -> FontMgr {
+        let datas: Vec<*const u8> = fonts.iter().map(|font| font.as_ptr()).collect();
+        let sizes: Vec<usize> = fonts.iter().map(|font| font.len()).collect();
+        FontMgr::from_ptr(unsafe {
+            C_SkFontMgr_New_Custom_Embedded(
+                datas.as_ptr(),
+                sizes.as_ptr(),
+                fonts.len().try_into().unwrap(),
+            )
+        })
+        .unwrap()
+    }
+
+    /// Creates a font manager that does not provide any fonts.
+    ///
+    /// Only available on Linux.
+    pub fn new_custom_empty() -> FontMgr {
+        FontMgr::from_ptr(unsafe { C_SkFontMgr_New_Custom_Empty() }).unwrap()
+    }
+}
+
 impl RCHandle<SkFontMgr> {
     pub fn count_families(&self) -> usize {
         unsafe { self.native().countFamilies().try_into().unwrap() }
@@ -158,6 +212,26 @@ impl RCHandle<SkFontMgr> {
         })
     }
 
+    /// Returns the typeface that best matches the family name and style. If `family_name`
+    /// is `None` or not found, the default typeface of the font manager is returned.
+    pub fn legacy_make_typeface(
+        &self,
+        family_name: Option<&str>,
+        style: FontStyle,
+    ) -> Option<Typeface> {
+        let family_name = family_name.and_then(|name| CString::new(name).ok());
+        Typeface::from_ptr(unsafe {
+            C_SkFontMgr_legacyMakeTypeface(
+                self.native(),
+                family_name
+                    .as_ref()
+                    .map(|name| name.as_ptr())
+                    .unwrap_or(std::ptr::null()),
+                *style.native(),
+            )
+        })
+    }
+
     #[deprecated(since = "0.12.0", note = "use new_from_data()")]
     pub fn new_from_bytes(&self, bytes: &[u8], ttc_index: Option<usize>) -> Option<Typeface> {
         self.new_from_data(bytes, ttc_index)
@@ -182,7 +256,180 @@ impl RCHandle<SkFontMgr> {
         })
     }
 
-    // TODO: makeFromStream(.., ttcIndex).
+    /// Creates a font manager that resolves fonts with `provider`.
+    pub fn from_provider(provider: impl FontMgrProvider + 'static) -> FontMgr {
+        // a thin pointer to pass through the C++ font manager.
+        let provider: Box<Box<dyn FontMgrProvider>> = Box::new(Box::new(provider));
+        FontMgr::from_ptr(unsafe {
+            C_RustFontMgr_new(
+                Box::into_raw(provider) as *mut c_void,
+                Some(count_families),
+                Some(get_family_name),
+                Some(match_family_style),
+                Some(match_family_style_character),
+                Some(make_from_data),
+                Some(drop_provider),
+            )
+        })
+        .unwrap()
+    }
+
+    /// Creates a typeface from the font data read from stream. Returns `Ok(None)` if the
+    /// data is not a supported font.
+    ///
+    /// Skia needs random access to the font data, so the stream is read completely.
+    /// Use new_from_seekable_stream() to read the font data only when it is needed.
+    pub fn new_from_stream(
+        &self,
+        stream: impl io::Read,
+        ttc_index: impl Into<Option<usize>>,
+    ) -> io::Result<Option<Typeface>> {
+        let data = Data::from_stream(stream, None)?;
+        Ok(Typeface::from_ptr(unsafe {
+            C_SkFontMgr_makeFromData(
+                self.native(),
+                data.shared_native(),
+                ttc_index.into().unwrap_or_default().try_into().unwrap(),
+            )
+        }))
+    }
+
+    /// Creates a typeface that reads its font data from stream as long as it lives.
+    pub fn new_from_seekable_stream(
+        &self,
+        stream: impl io::Read + io::Seek + 'static,
+        ttc_index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        let stream = RustStream::new_seekable(stream).ok()?;
+        Typeface::from_ptr(unsafe {
+            C_SkFontMgr_makeFromStream(
+                self.native(),
+                stream.into_native(),
+                ttc_index.into().unwrap_or_default().try_into().unwrap(),
+            )
+        })
+    }
+}
+
+/// Resolves the fonts of a font manager that is implemented in Rust.
+///
+/// The style sets of the families consist of the typefaces that match the normal, bold,
+/// italic, and bold italic styles.
+pub trait FontMgrProvider {
+    fn count_families(&self) -> usize;
+
+    fn family_name(&self, index: usize) -> String;
+
+    /// Returns the typeface of the family that best matches the style, or `None` if the
+    /// family is not known.
+    fn match_family_style(&self, family_name: &str, style: FontStyle) -> Option<Typeface>;
+
+    /// Returns a fallback typeface that is able to render `character`. `family_name` may be
+    /// empty and `bcp_47` lists the languages of the text, the most significant language last.
+    fn match_family_style_character(
+        &self,
+        _family_name: &str,
+        _style: FontStyle,
+        _bcp_47: &[&str],
+        _character: Unichar,
+    ) -> Option<Typeface> {
+        None
+    }
+
+    /// Creates a typeface from font data. By default, the typeface is created by the default
+    /// font manager.
+    fn new_from_data(&self, bytes: &[u8], ttc_index: usize) -> Option<Typeface> {
+        FontMgr::default().new_from_data(bytes, ttc_index)
+    }
+}
+
+unsafe fn provider<'a>(provider: *mut c_void) -> &'a dyn FontMgrProvider {
+    &**(provider as *const Box<dyn FontMgrProvider>)
+}
+
+unsafe extern "C" fn count_families(provider_ptr: *mut c_void) -> c_int {
+    catch_panic_or(0, || {
+        provider(provider_ptr).count_families().try_into().unwrap()
+    })
+}
+
+unsafe extern "C" fn get_family_name(
+    provider_ptr: *mut c_void,
+    index: c_int,
+    family_name: *mut SkString,
+) {
+    catch_panic_or((), || {
+        let name = provider(provider_ptr).family_name(index.try_into().unwrap());
+        (*family_name).set2(name.as_ptr() as _, name.len())
+    })
+}
+
+unsafe extern "C" fn match_family_style(
+    provider_ptr: *mut c_void,
+    family_name: *const c_char,
+    style: *const SkFontStyle,
+) -> *mut SkTypeface {
+    catch_panic_or(ptr::null_mut(), || {
+        let family_name = CStr::from_ptr(family_name).to_string_lossy();
+        provider(provider_ptr)
+            .match_family_style(&family_name, FontStyle::from_native(*style))
+            .shared_ptr() as _
+    })
+}
+
+unsafe extern "C" fn match_family_style_character(
+    provider_ptr: *mut c_void,
+    family_name: *const c_char,
+    style: *const SkFontStyle,
+    bcp_47: *mut *const c_char,
+    bcp_47_count: c_int,
+    character: Unichar,
+) -> *mut SkTypeface {
+    catch_panic_or(ptr::null_mut(), || {
+        let family_name = CStr::from_ptr(family_name).to_string_lossy();
+        let bcp_47: Vec<_> = if bcp_47.is_null() || bcp_47_count <= 0 {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(bcp_47, bcp_47_count.try_into().unwrap())
+                .iter()
+                .map(|language| CStr::from_ptr(*language).to_string_lossy())
+                .collect()
+        };
+        let bcp_47: Vec<&str> = bcp_47.iter().map(|language| language.as_ref()).collect();
+        provider(provider_ptr)
+            .match_family_style_character(
+                &family_name,
+                FontStyle::from_native(*style),
+                &bcp_47,
+                character,
+            )
+            .shared_ptr() as _
+    })
+}
+
+unsafe extern "C" fn make_from_data(
+    provider_ptr: *mut c_void,
+    data: *const u8,
+    length: usize,
+    ttc_index: c_int,
+) -> *mut SkTypeface {
+    catch_panic_or(ptr::null_mut(), || {
+        let bytes: &[u8] = if data.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(data, length)
+        };
+        provider(provider_ptr)
+            .new_from_data(bytes, ttc_index.try_into().unwrap_or_default())
+            .shared_ptr() as _
+    })
+}
+
+unsafe extern "C" fn drop_provider(provider: *mut c_void) {
+    // there is no one left to report a panic of the provider's drop to.
+    catch_panic_or((), || {
+        drop(Box::from_raw(provider as *mut Box<dyn FontMgrProvider>))
+    })
 }
 
 #[test]
@@ -205,3 +452,148 @@ fn create_all_typefaces() {
         }
     }
 }
+
+#[test]
+fn typefaces_from_streams() {
+    let font_mgr = FontMgr::default();
+    if let Some((data, ttc_index)) = Typeface::default().open_stream() {
+        let bytes = data.as_bytes().to_vec();
+        let typeface = font_mgr
+            .new_from_stream(&bytes[..], ttc_index)
+            .unwrap()
+            .unwrap();
+        let streamed = font_mgr
+            .new_from_seekable_stream(io::Cursor::new(bytes), ttc_index)
+            .unwrap();
+        assert_eq!(streamed.family_name(), typeface.family_name());
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn empty_custom_font_manager_does_not_match_families() {
+    let font_mgr = FontMgr::new_custom_empty();
+    assert!(font_mgr
+        .match_family_style("Arial", FontStyle::default())
+        .is_none());
+    // the default typeface of an empty font manager has no glyphs.
+    let typeface = font_mgr
+        .legacy_make_typeface(None, FontStyle::default())
+        .unwrap();
+    assert_eq!(typeface.count_glyphs(), 0);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn embedded_custom_font_manager_without_fonts() {
+    let font_mgr = FontMgr::new_custom_embedded(&[]);
+    assert!(font_mgr
+        .match_family_style("Arial", FontStyle::default())
+        .is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn directory_custom_font_manager_provides_the_fonts_of_the_directory() {
+    use std::{env, fs, process};
+
+    let typeface = Typeface::default();
+    let (data, _) = typeface.open_stream().unwrap();
+    let dir = env::temp_dir().join(format!("skia-safe-fonts-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("default.ttf"), data.as_bytes()).unwrap();
+
+    let font_mgr = FontMgr::new_custom_directory(&dir).unwrap();
+    let family_names: Vec<String> = (0..font_mgr.count_families())
+        .map(|index| font_mgr.family_name(index))
+        .collect();
+    let matched = font_mgr.match_family_style(typeface.family_name(), FontStyle::default());
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(family_names.contains(&typeface.family_name()));
+    assert!(matched.is_some());
+}
+
+#[test]
+fn font_mgr_from_provider() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct Provider {
+        font_mgr: FontMgr,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl Drop for Provider {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::SeqCst)
+        }
+    }
+
+    impl FontMgrProvider for Provider {
+        fn count_families(&self) -> usize {
+            1
+        }
+
+        fn family_name(&self, _index: usize) -> String {
+            "Licensed".into()
+        }
+
+        fn match_family_style(&self, family_name: &str, style: FontStyle) -> Option<Typeface> {
+            if family_name == "Licensed" {
+                self.font_mgr.legacy_make_typeface(None, style)
+            } else {
+                None
+            }
+        }
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let font_mgr = FontMgr::from_provider(Provider {
+        font_mgr: FontMgr::default(),
+        dropped: dropped.clone(),
+    });
+    assert_eq!(font_mgr.count_families(), 1);
+    assert_eq!(font_mgr.family_name(0), "Licensed");
+    assert!(font_mgr
+        .match_family_style("Licensed", FontStyle::bold())
+        .is_some());
+    assert!(font_mgr
+        .match_family_style("Other", FontStyle::bold())
+        .is_none());
+    assert!(font_mgr.new_styleset(0).count() > 0);
+    assert!(font_mgr
+        .legacy_make_typeface(None, FontStyle::default())
+        .is_some());
+    assert!(font_mgr
+        .match_family_style_character("", FontStyle::default(), &["en"], 'a' as Unichar)
+        .is_none());
+
+    drop(font_mgr);
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn panics_of_providers_are_not_unwound_into_skia() {
+    struct Provider;
+
+    impl FontMgrProvider for Provider {
+        fn count_families(&self) -> usize {
+            panic!("count_families")
+        }
+
+        fn family_name(&self, _index: usize) -> String {
+            panic!("family_name")
+        }
+
+        fn match_family_style(&self, _family_name: &str, _style: FontStyle) -> Option<Typeface> {
+            panic!("match_family_style")
+        }
+    }
+
+    let font_mgr = FontMgr::from_provider(Provider);
+    assert_eq!(font_mgr.count_families(), 0);
+    assert!(font_mgr
+        .match_family_style("Licensed", FontStyle::default())
+        .is_none());
+}
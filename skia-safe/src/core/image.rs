@@ -525,3 +525,19 @@ impl RCHandle<SkImage> {
         })
     }
 }
+
+#[cfg(all(test, feature = "webp"))]
+mod webp_tests {
+    use crate::{Color, EncodedImageFormat, Image, Surface};
+
+    #[test]
+    fn webp_round_trip() {
+        let mut surface = Surface::new_raster_n32_premul((32, 16)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+        let data = image.encode_to_data(EncodedImageFormat::WEBP).unwrap();
+        assert_eq!(&data.as_bytes()[8..12], b"WEBP");
+        let decoded = Image::from_encoded(&data, None).unwrap();
+        assert_eq!(decoded.dimensions(), image.dimensions());
+    }
+}
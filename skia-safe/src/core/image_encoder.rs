@@ -1,6 +1,13 @@
-use crate::prelude::Handle;
-use crate::{Data, EncodedImageFormat};
-use skia_bindings::{SkBitmap, SkPixmap};
+use crate::interop::{DynamicMemoryWStream, RustWStream};
+use crate::prelude::*;
+use crate::{Data, DataTable, EncodedImageFormat, Pixmap};
+use skia_bindings::{
+    C_SkJpegEncoder_Encode, C_SkPngEncoder_Encode, C_SkWebpEncoder_Encode, SkBitmap,
+    SkJpegEncoder_AlphaOption, SkJpegEncoder_Downsample, SkPixmap, SkPngEncoder_FilterFlag,
+    SkWStream, SkWebpEncoder_Compression,
+};
+use std::ffi::{CString, NulError};
+use std::io;
 
 impl Handle<SkPixmap> {
     pub fn encode(&self, format: EncodedImageFormat, quality: usize) -> Option<Data> {
@@ -14,12 +21,236 @@ impl Handle<SkBitmap> {
     }
 }
 
-pub mod encode {
-    // TODO: wrap stream variants.
+bitflags! {
+    pub struct PngFilterFlags: u32 {
+        const NONE = SkPngEncoder_FilterFlag::kNone as _;
+        const SUB = SkPngEncoder_FilterFlag::kSub as _;
+        const UP = SkPngEncoder_FilterFlag::kUp as _;
+        const AVG = SkPngEncoder_FilterFlag::kAvg as _;
+        const PAETH = SkPngEncoder_FilterFlag::kPaeth as _;
+        const ALL = Self::NONE.bits | Self::SUB.bits | Self::UP.bits | Self::AVG.bits | Self::PAETH.bits;
+    }
+}
+
+/// Encodes pixmaps to PNG.
+///
+/// Premultiplied pixels are unpremultiplied before they are encoded. How they are
+/// unpremultiplied can't be configured, because `SkPngEncoder::Options` of the Skia version
+/// the bindings are based on (m75) have no unpremul behavior option.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PngEncoder {
+    /// The filtering strategies the encoder chooses from.
+    pub filter_flags: PngFilterFlags,
+    /// Must be in [0, 9], where 9 is the maximal compression, otherwise encoding fails.
+    pub z_lib_level: usize,
+    /// Keyword / text pairs written to tEXt chunks. Encoding fails if they contain zero bytes.
+    pub comments: Vec<(String, String)>,
+}
+
+impl Default for PngEncoder {
+    fn default() -> Self {
+        PngEncoder {
+            filter_flags: PngFilterFlags::ALL,
+            z_lib_level: 6,
+            comments: Vec::new(),
+        }
+    }
+}
+
+impl PngEncoder {
+    pub fn encode(&self, src: &Pixmap) -> Option<Data> {
+        encode_to_data(|stream| self.encode_to_stream(stream, src))
+    }
+
+    pub fn encode_to_writer(&self, src: &Pixmap, writer: &mut impl io::Write) -> io::Result<()> {
+        encode_to_writer(writer, |stream| self.encode_to_stream(stream, src))
+    }
+
+    pub(crate) fn encode_to_stream(&self, dst: &mut SkWStream, src: &Pixmap) -> bool {
+        if self.z_lib_level > 9 {
+            return false;
+        }
+        let comments = match self.comments_data_table() {
+            Ok(comments) => comments,
+            Err(_) => return false,
+        };
+        unsafe {
+            C_SkPngEncoder_Encode(
+                dst,
+                src.native(),
+                self.filter_flags.bits().try_into().unwrap(),
+                self.z_lib_level.try_into().unwrap(),
+                comments.as_ref().native_ptr_or_null(),
+            )
+        }
+    }
+
+    fn comments_data_table(&self) -> Result<Option<DataTable>, NulError> {
+        if self.comments.is_empty() {
+            return Ok(None);
+        }
+        // the strings in the table must be zero terminated.
+        let strings: Vec<CString> = self
+            .comments
+            .iter()
+            .flat_map(|(keyword, text)| vec![keyword, text])
+            .map(|s| CString::new(s.as_str()))
+            .collect::<Result<_, _>>()?;
+        let slices: Vec<&[u8]> = strings.iter().map(|s| s.as_bytes_with_nul()).collect();
+        Ok(Some(DataTable::from_slices(&slices)))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum JpegDownsample {
+    BothDirections = SkJpegEncoder_Downsample::k420 as _,
+    Horizontal = SkJpegEncoder_Downsample::k422 as _,
+    No = SkJpegEncoder_Downsample::k444 as _,
+}
+
+impl NativeTransmutable<SkJpegEncoder_Downsample> for JpegDownsample {}
+#[test]
+fn test_jpeg_downsample_layout() {
+    JpegDownsample::test_layout()
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum JpegAlphaOption {
+    Ignore = SkJpegEncoder_AlphaOption::kIgnore as _,
+    BlendOnBlack = SkJpegEncoder_AlphaOption::kBlendOnBlack as _,
+}
+
+impl NativeTransmutable<SkJpegEncoder_AlphaOption> for JpegAlphaOption {}
+#[test]
+fn test_jpeg_alpha_option_layout() {
+    JpegAlphaOption::test_layout()
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct JpegEncoder {
+    /// Must be in [0, 100], otherwise encoding fails.
+    pub quality: usize,
+    pub downsample: JpegDownsample,
+    pub alpha_option: JpegAlphaOption,
+}
+
+impl Default for JpegEncoder {
+    fn default() -> Self {
+        JpegEncoder {
+            quality: 100,
+            downsample: JpegDownsample::BothDirections,
+            alpha_option: JpegAlphaOption::Ignore,
+        }
+    }
+}
+
+impl JpegEncoder {
+    pub fn encode(&self, src: &Pixmap) -> Option<Data> {
+        encode_to_data(|stream| self.encode_to_stream(stream, src))
+    }
+
+    pub fn encode_to_writer(&self, src: &Pixmap, writer: &mut impl io::Write) -> io::Result<()> {
+        encode_to_writer(writer, |stream| self.encode_to_stream(stream, src))
+    }
+
+    pub(crate) fn encode_to_stream(&self, dst: &mut SkWStream, src: &Pixmap) -> bool {
+        if self.quality > 100 {
+            return false;
+        }
+        unsafe {
+            C_SkJpegEncoder_Encode(
+                dst,
+                src.native(),
+                self.quality.try_into().unwrap(),
+                self.downsample.into_native(),
+                self.alpha_option.into_native(),
+            )
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum WebpCompression {
+    Lossy = SkWebpEncoder_Compression::kLossy as _,
+    Lossless = SkWebpEncoder_Compression::kLossless as _,
+}
+
+impl NativeTransmutable<SkWebpEncoder_Compression> for WebpCompression {}
+#[test]
+fn test_webp_compression_layout() {
+    WebpCompression::test_layout()
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WebpEncoder {
+    pub compression: WebpCompression,
+    /// Must be in [0, 100], otherwise encoding fails. For lossy compression, this is the visual
+    /// quality, for lossless compression, the effort put into reducing the size.
+    pub quality: f32,
+}
+
+impl Default for WebpEncoder {
+    fn default() -> Self {
+        WebpEncoder {
+            compression: WebpCompression::Lossy,
+            quality: 100.0,
+        }
+    }
+}
+
+impl WebpEncoder {
+    pub fn encode(&self, src: &Pixmap) -> Option<Data> {
+        encode_to_data(|stream| self.encode_to_stream(stream, src))
+    }
 
+    pub fn encode_to_writer(&self, src: &Pixmap, writer: &mut impl io::Write) -> io::Result<()> {
+        encode_to_writer(writer, |stream| self.encode_to_stream(stream, src))
+    }
+
+    pub(crate) fn encode_to_stream(&self, dst: &mut SkWStream, src: &Pixmap) -> bool {
+        if !(0.0..=100.0).contains(&self.quality) {
+            return false;
+        }
+        unsafe {
+            C_SkWebpEncoder_Encode(
+                dst,
+                src.native(),
+                self.compression.into_native(),
+                self.quality,
+            )
+        }
+    }
+}
+
+fn encode_to_data(encode: impl FnOnce(&mut SkWStream) -> bool) -> Option<Data> {
+    let mut stream = DynamicMemoryWStream::new();
+    encode(&mut stream.native_mut()._base).if_true_then_some(|| stream.detach_as_data())
+}
+
+fn encode_to_writer(
+    writer: &mut impl io::Write,
+    encode: impl FnOnce(&mut SkWStream) -> bool,
+) -> io::Result<()> {
+    let mut stream = RustWStream::new(writer);
+    let encoded = encode(stream.stream_mut());
+    match stream.take_error() {
+        Some(error) => Err(error),
+        None if encoded => Ok(()),
+        None => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "failed to encode image",
+        )),
+    }
+}
+
+pub mod encode {
     use crate::prelude::*;
     use crate::{Bitmap, Data, EncodedImageFormat, Pixmap};
-    use skia_bindings::{C_SkEncodeBitmap, C_SkEncodePixmap};
+    use skia_bindings::{C_SkEncodeBitmap, C_SkEncodeImage, C_SkEncodePixmap};
+    use std::io;
 
     pub fn pixmap(src: &Pixmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
         Data::from_ptr(unsafe {
@@ -40,4 +271,168 @@ pub mod encode {
             )
         })
     }
+
+    /// Encodes the pixmap and writes the encoded bytes to writer while they are produced.
+    pub fn pixmap_to_writer(
+        src: &Pixmap,
+        format: EncodedImageFormat,
+        quality: usize,
+        writer: &mut impl io::Write,
+    ) -> io::Result<()> {
+        super::encode_to_writer(writer, |stream| unsafe {
+            C_SkEncodeImage(
+                stream,
+                src.native(),
+                format.into_native(),
+                quality.try_into().unwrap(),
+            )
+        })
+    }
+
+    pub fn bitmap_to_writer(
+        src: &Bitmap,
+        format: EncodedImageFormat,
+        quality: usize,
+        writer: &mut impl io::Write,
+    ) -> io::Result<()> {
+        match src.peek_pixels() {
+            Some(pixmap) => pixmap_to_writer(&pixmap, format, quality, writer),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "bitmap has no pixels",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, JpegDownsample, JpegEncoder, PngEncoder, WebpEncoder};
+    use crate::{Bitmap, Codec, Color, EncodedImageFormat};
+    use std::io;
+
+    fn bitmap() -> Bitmap {
+        let mut bitmap = Bitmap::new();
+        bitmap.alloc_n32_pixels((16, 16), None);
+        bitmap.erase_color(Color::GREEN);
+        bitmap
+    }
+
+    #[test]
+    fn encode_png_with_comments() {
+        let encoder = PngEncoder {
+            z_lib_level: 9,
+            comments: vec![("Author".into(), "rust-skia".into())],
+            ..PngEncoder::default()
+        };
+        let data = encoder.encode(bitmap().pixmap()).unwrap();
+        let codec = Codec::from_data(&data).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::PNG);
+        assert!(data
+            .as_bytes()
+            .windows(b"rust-skia".len())
+            .any(|w| w == b"rust-skia"));
+    }
+
+    #[test]
+    fn png_comments_with_zero_bytes_fail() {
+        let encoder = PngEncoder {
+            comments: vec![("Author".into(), "rust\0skia".into())],
+            ..PngEncoder::default()
+        };
+        assert!(encoder.encode(bitmap().pixmap()).is_none());
+    }
+
+    #[test]
+    fn invalid_encoder_options_fail() {
+        let png = PngEncoder {
+            z_lib_level: 10,
+            ..PngEncoder::default()
+        };
+        assert!(png.encode(bitmap().pixmap()).is_none());
+        let jpeg = JpegEncoder {
+            quality: 101,
+            ..JpegEncoder::default()
+        };
+        assert!(jpeg.encode(bitmap().pixmap()).is_none());
+        let webp = WebpEncoder {
+            quality: -1.0,
+            ..WebpEncoder::default()
+        };
+        assert!(webp.encode(bitmap().pixmap()).is_none());
+        let mut writer = Vec::new();
+        assert!(png
+            .encode_to_writer(bitmap().pixmap(), &mut writer)
+            .is_err());
+    }
+
+    #[test]
+    fn encode_jpeg_without_downsampling() {
+        let encoder = JpegEncoder {
+            quality: 90,
+            downsample: JpegDownsample::No,
+            ..JpegEncoder::default()
+        };
+        let data = encoder.encode(bitmap().pixmap()).unwrap();
+        let codec = Codec::from_data(&data).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::JPEG);
+        assert_eq!(codec.dimensions(), (16, 16).into());
+    }
+
+    #[cfg(feature = "webp")]
+    #[test]
+    fn webp_round_trip() {
+        use super::WebpCompression;
+        use crate::{ImageInfo, Pixmap};
+
+        let encoder = WebpEncoder {
+            compression: WebpCompression::Lossless,
+            ..WebpEncoder::default()
+        };
+        let data = encoder.encode(bitmap().pixmap()).unwrap();
+        let mut codec = Codec::from_data(&data).unwrap();
+        assert_eq!(codec.encoded_format(), EncodedImageFormat::WEBP);
+        assert_eq!(codec.dimensions(), (16, 16).into());
+
+        let info = ImageInfo::new_n32_premul(codec.dimensions(), None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut pixmap = Pixmap::new(&info, &mut pixels, info.min_row_bytes());
+        assert_eq!(
+            codec.get_pixels_to_pixmap(&mut pixmap, None),
+            crate::codec::codec::Result::Success
+        );
+        assert_eq!(pixmap.get_color((8, 8)), Color::GREEN);
+    }
+
+    #[test]
+    fn encode_to_writer() {
+        let mut png = Vec::new();
+        encode::bitmap_to_writer(&bitmap(), EncodedImageFormat::PNG, 100, &mut png).unwrap();
+        assert_eq!(
+            png,
+            encode::bitmap(&bitmap(), EncodedImageFormat::PNG, 100)
+                .unwrap()
+                .as_bytes()
+        );
+    }
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn encode_to_failing_writer_reports_io_error() {
+        let error = PngEncoder::default()
+            .encode_to_writer(bitmap().pixmap(), &mut FailingWriter)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
 }
@@ -1,11 +1,14 @@
+use crate::interop::RustStream;
 use crate::prelude::*;
 use crate::{Canvas, Data, Rect};
 use crate::{Matrix, Shader, TileMode};
 use skia_bindings::{
-    C_SkPicture_MakeFromData, C_SkPicture_MakeFromData2, C_SkPicture_MakePlaceholder,
-    C_SkPicture_approximateBytesUsed, C_SkPicture_approximateOpCount, C_SkPicture_cullRect,
-    C_SkPicture_makeShader, C_SkPicture_playback, C_SkPicture_serialize, SkPicture, SkRefCntBase,
+    C_SkPicture_MakeFromData, C_SkPicture_MakeFromData2, C_SkPicture_MakeFromStream,
+    C_SkPicture_MakePlaceholder, C_SkPicture_approximateBytesUsed, C_SkPicture_approximateOpCount,
+    C_SkPicture_cullRect, C_SkPicture_makeShader, C_SkPicture_playback, C_SkPicture_serialize,
+    SkPicture, SkRefCntBase,
 };
+use std::io;
 
 pub type Picture = RCHandle<SkPicture>;
 
@@ -18,8 +21,6 @@ impl NativeRefCountedBase for SkPicture {
 }
 
 impl RCHandle<SkPicture> {
-    // TODO: wrap MakeFromStream
-
     // TODO: may support SkSerialProces in MakeFromData?
 
     pub fn from_data(data: &Data) -> Option<Picture> {
@@ -30,6 +31,19 @@ impl RCHandle<SkPicture> {
         Picture::from_ptr(unsafe { C_SkPicture_MakeFromData2(bytes.as_ptr() as _, bytes.len()) })
     }
 
+    pub fn from_stream(stream: impl io::Read) -> io::Result<Picture> {
+        let mut stream = RustStream::new(stream);
+        let picture = Picture::from_ptr(unsafe { C_SkPicture_MakeFromStream(stream.stream_mut()) });
+        match (stream.take_error(), picture) {
+            (Some(error), _) => Err(error),
+            (None, Some(picture)) => Ok(picture),
+            (None, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "failed to read picture",
+            )),
+        }
+    }
+
     // TODO: AbortCallback and the function that use it.
 
     pub fn playback(&self, mut canvas: impl AsMut<Canvas>) {
@@ -94,3 +108,45 @@ impl RCHandle<SkPicture> {
         .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Paint, Picture, PictureRecorder, Rect};
+    use std::io;
+
+    fn picture() -> Picture {
+        let mut recorder = PictureRecorder::new();
+        recorder
+            .begin_recording(Rect::new(0.0, 0.0, 10.0, 10.0), None, None)
+            .draw_rect(Rect::new(0.0, 0.0, 5.0, 5.0), &Paint::default());
+        recorder.finish_recording_as_picture(None).unwrap()
+    }
+
+    #[test]
+    fn picture_from_stream() {
+        let serialized = picture().serialize();
+        let picture = Picture::from_stream(serialized.as_bytes()).unwrap();
+        assert_eq!(picture.cull_rect(), Rect::new(0.0, 0.0, 10.0, 10.0));
+        let truncated = &serialized.as_bytes()[..serialized.size() / 2];
+        assert_eq!(
+            Picture::from_stream(truncated).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn picture_from_stream_reports_reader_errors() {
+        struct FailingReader;
+
+        impl io::Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+            }
+        }
+
+        assert_eq!(
+            Picture::from_stream(FailingReader).unwrap_err().kind(),
+            io::ErrorKind::ConnectionReset
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use crate::{scalar, Font, GlyphId, Paint, Point, Rect, TextEncoding};
+use crate::{scalar, Font, GlyphId, Paint, Point, RSXform, Rect, TextEncoding};
 use skia_bindings::{
     C_SkTextBlobBuilder_destruct, C_SkTextBlobBuilder_make, C_SkTextBlob_MakeFromText, SkTextBlob,
     SkTextBlobBuilder,
@@ -148,6 +148,28 @@ impl Handle<SkTextBlobBuilder> {
             )
         }
     }
+
+    /// Allocates a run in which every glyph is positioned, rotated and scaled by its
+    /// own transformation.
+    pub fn alloc_run_rsxform(
+        &mut self,
+        font: &Font,
+        count: usize,
+    ) -> (&mut [GlyphId], &mut [RSXform]) {
+        // the buffers of an empty run are null.
+        if count == 0 {
+            return (&mut [], &mut []);
+        }
+        unsafe {
+            let buffer = self
+                .native_mut()
+                .allocRunRSXform(font.native(), count.try_into().unwrap());
+            (
+                slice::from_raw_parts_mut((*buffer).glyphs, count),
+                slice::from_raw_parts_mut((*buffer).pos as *mut RSXform, count),
+            )
+        }
+    }
 }
 
 #[test]
@@ -155,3 +177,35 @@ fn test_point_size_equals_size_of_two_scalars_used_in_alloc_run_pos() {
     use std::mem;
     assert_eq!(mem::size_of::<Point>(), mem::size_of::<[scalar; 2]>())
 }
+
+#[test]
+fn test_rsxform_size_equals_size_of_four_scalars_used_in_alloc_run_rsxform() {
+    use std::mem;
+    assert_eq!(mem::size_of::<RSXform>(), mem::size_of::<[scalar; 4]>())
+}
+
+#[test]
+fn build_blob_with_rsxform_run() {
+    let font = Font::default();
+    let glyphs = font.str_to_glyphs_vec("Hi");
+    let mut builder = TextBlobBuilder::new();
+    {
+        let (run_glyphs, xforms) = builder.alloc_run_rsxform(&font, glyphs.len());
+        run_glyphs.copy_from_slice(&glyphs);
+        for (i, xform) in xforms.iter_mut().enumerate() {
+            *xform = RSXform::from_radians(1.0, 0.5, (i as scalar * 10.0, 20.0), (0, 0));
+        }
+    }
+    assert!(builder.make().is_some());
+}
+
+#[test]
+fn empty_rsxform_run() {
+    let mut builder = TextBlobBuilder::new();
+    {
+        let (glyphs, xforms) = builder.alloc_run_rsxform(&Font::default(), 0);
+        assert!(glyphs.is_empty());
+        assert!(xforms.is_empty());
+    }
+    assert!(builder.make().is_none());
+}
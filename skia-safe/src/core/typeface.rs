@@ -1,15 +1,17 @@
-use crate::interop::{MemoryStream, NativeStreamBase};
+use crate::interop::{MemoryStream, NativeStreamBase, RustStream};
 use crate::prelude::*;
 use crate::{font_arguments, interop, FontArguments};
-use crate::{font_parameters::VariationAxis, Data, FontStyle, GlyphId, Rect, Unichar};
+use crate::{font_parameters::VariationAxis, scalar, Data, FontStyle, GlyphId, Rect, Unichar};
 use skia_bindings::{
-    C_SkTypeface_LocalizedStrings_next, C_SkTypeface_LocalizedStrings_unref,
+    C_SkFontData_delete, C_SkFontData_getAxis, C_SkFontData_getAxisCount, C_SkFontData_getData,
+    C_SkFontData_getIndex, C_SkTypeface_LocalizedStrings_next, C_SkTypeface_LocalizedStrings_unref,
     C_SkTypeface_MakeDefault, C_SkTypeface_MakeDeserialize, C_SkTypeface_MakeFromData,
-    C_SkTypeface_MakeFromName, C_SkTypeface_isBold, C_SkTypeface_isItalic, C_SkTypeface_makeClone,
-    C_SkTypeface_serialize, SkRefCntBase, SkTypeface, SkTypeface_LocalizedStrings,
-    SkTypeface_SerializeBehavior,
+    C_SkTypeface_MakeFromName, C_SkTypeface_MakeFromStream, C_SkTypeface_isBold,
+    C_SkTypeface_isItalic, C_SkTypeface_makeClone, C_SkTypeface_makeFontData,
+    C_SkTypeface_openStream, C_SkTypeface_serialize, SkFontData, SkRefCntBase, SkTypeface,
+    SkTypeface_LocalizedStrings, SkTypeface_SerializeBehavior,
 };
-use std::{ffi, ptr};
+use std::{ffi, io, ptr, slice};
 
 pub type FontId = skia_bindings::SkFontID;
 pub type FontTableTag = skia_bindings::SkFontTableTag;
@@ -34,6 +36,17 @@ pub struct LocalizedString {
     pub language: String,
 }
 
+/// The font file of a typeface and the values of its variation axes.
+#[derive(Clone)]
+pub struct FontData {
+    /// The data of the font file.
+    pub data: Data,
+    /// The index of the font in a font collection (ttc) file.
+    pub index: usize,
+    /// The values of the variation axes, in the order of the font's axes.
+    pub axes: Vec<scalar>,
+}
+
 pub type Typeface = RCHandle<SkTypeface>;
 
 impl NativeRefCountedBase for SkTypeface {
@@ -107,6 +120,21 @@ impl RCHandle<SkTypeface> {
         }
     }
 
+    /// Returns the coordinates of the default instance of a variable font, which are
+    /// the default values of its axes.
+    pub fn variation_design_defaults(
+        &self,
+    ) -> Option<Vec<font_arguments::variation_position::Coordinate>> {
+        self.variation_design_parameters().map(|axes| {
+            axes.iter()
+                .map(|axis| font_arguments::variation_position::Coordinate {
+                    axis: axis.tag,
+                    value: axis.def,
+                })
+                .collect()
+        })
+    }
+
     pub fn unique_id(self) -> FontId {
         unsafe { self.native().uniqueID() }
     }
@@ -129,15 +157,40 @@ impl RCHandle<SkTypeface> {
     // from_file is unsupported, because it is unclear what the
     // encoding of the path name is. from_data can be used instead.
 
-    // TODO: MakeFromStream()?
+    /// Creates a typeface from the font data read from stream. Returns `Ok(None)` if the
+    /// data is not a supported font.
+    ///
+    /// Skia needs random access to the font data, so the stream is read completely.
+    /// Use from_seekable_stream() to read the font data only when it is needed.
+    pub fn from_stream(
+        stream: impl io::Read,
+        index: impl Into<Option<usize>>,
+    ) -> io::Result<Option<Typeface>> {
+        let data = Data::from_stream(stream, None)?;
+        Ok(Self::from_data(&data, index))
+    }
+
+    /// Creates a typeface that reads its font data from stream as long as it lives.
+    pub fn from_seekable_stream(
+        stream: impl io::Read + io::Seek + 'static,
+        index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        let stream = RustStream::new_seekable(stream).ok()?;
+        Typeface::from_ptr(unsafe {
+            C_SkTypeface_MakeFromStream(
+                stream.into_native(),
+                index.into().unwrap_or_default().try_into().unwrap(),
+            )
+        })
+    }
 
-    pub fn from_data(data: &Data, index: impl Into<Option<usize>>) {
+    pub fn from_data(data: &Data, index: impl Into<Option<usize>>) -> Option<Typeface> {
         Typeface::from_ptr(unsafe {
             C_SkTypeface_MakeFromData(
                 data.shared_native(),
                 index.into().unwrap_or_default().try_into().unwrap(),
             )
-        });
+        })
     }
 
     pub fn clone_with_arguments(&self, arguments: &FontArguments) -> Option<Typeface> {
@@ -208,6 +261,10 @@ impl RCHandle<SkTypeface> {
         self.get_table_data(tag, data)
     }
 
+    /// Copies the start of the table `tag` into data and returns the number of bytes copied.
+    ///
+    /// Tables that are not interpreted by Skia, like the named instances in the `fvar` table
+    /// of a variable font, can be read this way and parsed with a font parsing crate.
     pub fn get_table_data(&self, tag: FontTableTag, data: &mut [u8]) -> usize {
         unsafe {
             self.native()
@@ -257,8 +314,27 @@ impl RCHandle<SkTypeface> {
         name.as_str().into()
     }
 
-    // TODO: openStream()
-    // TODO: makeFontData()
+    /// Returns the data of the font file and the index of the font in a font collection (ttc)
+    /// file, or `None` if the font file is not accessible.
+    pub fn open_stream(&self) -> Option<(Data, usize)> {
+        let mut ttc_index = 0;
+        let data =
+            Data::from_ptr(unsafe { C_SkTypeface_openStream(self.native(), &mut ttc_index) })?;
+        Some((data, ttc_index.try_into().unwrap()))
+    }
+
+    /// Returns the font file and the current values of the variation axes, or `None` if the
+    /// font file is not accessible.
+    pub fn font_data(&self) -> Option<FontData> {
+        let font_data = unsafe { C_SkTypeface_makeFontData(self.native()) };
+        if font_data.is_null() {
+            return None;
+        }
+        let r = unsafe { read_font_data(font_data) };
+        unsafe { C_SkFontData_delete(font_data) };
+        r
+    }
+
     // TODO: createScalerContext()
 
     pub fn bounds(&self) -> Rect {
@@ -266,6 +342,24 @@ impl RCHandle<SkTypeface> {
     }
 }
 
+unsafe fn read_font_data(font_data: *mut SkFontData) -> Option<FontData> {
+    let data = Data::from_ptr(C_SkFontData_getData(font_data))?;
+    let axis_count = C_SkFontData_getAxisCount(font_data) as usize;
+    let axes = if axis_count > 0 {
+        slice::from_raw_parts(C_SkFontData_getAxis(font_data), axis_count)
+            .iter()
+            .map(|&fixed| fixed as scalar / 65536.0)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Some(FontData {
+        data,
+        index: C_SkFontData_getIndex(font_data).try_into().unwrap(),
+        axes,
+    })
+}
+
 #[repr(transparent)]
 struct LocalizedStringsIter(*mut SkTypeface_LocalizedStrings);
 
@@ -328,3 +422,32 @@ fn family_name_iterator_owns_the_strings_and_returns_at_least_one_name_for_the_d
     }
     assert!(any);
 }
+
+#[test]
+fn open_stream_of_the_default_typeface() {
+    let tf = Typeface::default();
+    if let Some((data, ttc_index)) = tf.open_stream() {
+        assert!(!data.is_empty());
+        let font_data = tf.font_data().unwrap();
+        assert_eq!(font_data.index, ttc_index);
+        assert_eq!(font_data.data.as_bytes(), data.as_bytes());
+        let reloaded = Typeface::from_data(&data, ttc_index).unwrap();
+        assert_eq!(reloaded.family_name(), tf.family_name());
+    }
+}
+
+#[test]
+fn typeface_from_streams() {
+    let tf = Typeface::default();
+    if let Some((data, ttc_index)) = tf.open_stream() {
+        let bytes = data.as_bytes().to_vec();
+        let from_stream = Typeface::from_stream(&bytes[..], ttc_index)
+            .unwrap()
+            .unwrap();
+        assert_eq!(from_stream.family_name(), tf.family_name());
+        let from_seekable_stream =
+            Typeface::from_seekable_stream(io::Cursor::new(bytes), ttc_index).unwrap();
+        assert_eq!(from_seekable_stream.family_name(), tf.family_name());
+        assert_eq!(from_seekable_stream.count_glyphs(), tf.count_glyphs());
+    }
+}
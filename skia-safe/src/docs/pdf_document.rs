@@ -1,14 +1,113 @@
 pub mod pdf {
-    use crate::interop::DynamicMemoryWStream;
+    use crate::document::{sink, state};
     use crate::prelude::*;
-    use crate::{scalar, DateTime, Document};
+    use crate::{scalar, Canvas, DateTime, Document};
     use skia_bindings::{
         C_SkPDF_MakeDocument, C_SkPDF_Metadata_Construct, C_SkPDF_Metadata_destruct,
-        SkPDF_Metadata, SkString,
+        C_SkPDF_SetNodeId, SkDocument, SkPDF_DocumentStructureType, SkPDF_Metadata,
+        SkPDF_StructureElementNode, SkString, SkWStream,
     };
+    use std::{io, ptr};
 
-    // TODO: DocumentStructureType
-    // TODO: StructureElementNode
+    /// The type of a structure element in a tagged PDF, as defined in
+    /// section 14.8.4 of the PDF 1.7 specification.
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[repr(i32)]
+    pub enum DocumentStructureType {
+        Document = SkPDF_DocumentStructureType::kDocument as _,
+        Part = SkPDF_DocumentStructureType::kPart as _,
+        Art = SkPDF_DocumentStructureType::kArt as _,
+        Sect = SkPDF_DocumentStructureType::kSect as _,
+        Div = SkPDF_DocumentStructureType::kDiv as _,
+        BlockQuote = SkPDF_DocumentStructureType::kBlockQuote as _,
+        Caption = SkPDF_DocumentStructureType::kCaption as _,
+        TOC = SkPDF_DocumentStructureType::kTOC as _,
+        TOCI = SkPDF_DocumentStructureType::kTOCI as _,
+        Index = SkPDF_DocumentStructureType::kIndex as _,
+        NonStruct = SkPDF_DocumentStructureType::kNonStruct as _,
+        Private = SkPDF_DocumentStructureType::kPrivate as _,
+        H = SkPDF_DocumentStructureType::kH as _,
+        H1 = SkPDF_DocumentStructureType::kH1 as _,
+        H2 = SkPDF_DocumentStructureType::kH2 as _,
+        H3 = SkPDF_DocumentStructureType::kH3 as _,
+        H4 = SkPDF_DocumentStructureType::kH4 as _,
+        H5 = SkPDF_DocumentStructureType::kH5 as _,
+        H6 = SkPDF_DocumentStructureType::kH6 as _,
+        P = SkPDF_DocumentStructureType::kP as _,
+        L = SkPDF_DocumentStructureType::kL as _,
+        LI = SkPDF_DocumentStructureType::kLI as _,
+        Lbl = SkPDF_DocumentStructureType::kLbl as _,
+        LBody = SkPDF_DocumentStructureType::kLBody as _,
+        Table = SkPDF_DocumentStructureType::kTable as _,
+        TR = SkPDF_DocumentStructureType::kTR as _,
+        TH = SkPDF_DocumentStructureType::kTH as _,
+        TD = SkPDF_DocumentStructureType::kTD as _,
+        THead = SkPDF_DocumentStructureType::kTHead as _,
+        TBody = SkPDF_DocumentStructureType::kTBody as _,
+        TFoot = SkPDF_DocumentStructureType::kTFoot as _,
+        Span = SkPDF_DocumentStructureType::kSpan as _,
+        Quote = SkPDF_DocumentStructureType::kQuote as _,
+        Note = SkPDF_DocumentStructureType::kNote as _,
+        Reference = SkPDF_DocumentStructureType::kReference as _,
+        BibEntry = SkPDF_DocumentStructureType::kBibEntry as _,
+        Code = SkPDF_DocumentStructureType::kCode as _,
+        Link = SkPDF_DocumentStructureType::kLink as _,
+        Annot = SkPDF_DocumentStructureType::kAnnot as _,
+        Ruby = SkPDF_DocumentStructureType::kRuby as _,
+        RB = SkPDF_DocumentStructureType::kRB as _,
+        RT = SkPDF_DocumentStructureType::kRT as _,
+        RP = SkPDF_DocumentStructureType::kRP as _,
+        Warichu = SkPDF_DocumentStructureType::kWarichu as _,
+        WT = SkPDF_DocumentStructureType::kWT as _,
+        WP = SkPDF_DocumentStructureType::kWP as _,
+        Figure = SkPDF_DocumentStructureType::kFigure as _,
+        Formula = SkPDF_DocumentStructureType::kFormula as _,
+        Form = SkPDF_DocumentStructureType::kForm as _,
+    }
+
+    impl NativeTransmutable<SkPDF_DocumentStructureType> for DocumentStructureType {}
+    #[test]
+    fn test_document_structure_type_layout() {
+        DocumentStructureType::test_layout()
+    }
+
+    impl Default for DocumentStructureType {
+        fn default() -> Self {
+            DocumentStructureType::NonStruct
+        }
+    }
+
+    /// A node in the structure element tree of a tagged PDF.
+    ///
+    /// Drawing commands are associated with a node by calling set_node_id() with
+    /// the node's id before they are issued.
+    ///
+    /// Alternate text can not be specified, because the `SkPDF::StructureElementNode` of the
+    /// Skia milestone this crate is based on (m75) has no field for it.
+    #[derive(Clone, PartialEq, Eq, Default, Debug)]
+    pub struct StructureElementNode {
+        pub structure_type: DocumentStructureType,
+        pub node_id: i32,
+        pub children: Vec<StructureElementNode>,
+    }
+
+    impl StructureElementNode {
+        pub fn new(structure_type: DocumentStructureType, node_id: i32) -> Self {
+            StructureElementNode {
+                structure_type,
+                node_id,
+                children: Vec::new(),
+            }
+        }
+
+        pub fn with_children(
+            mut self,
+            children: impl IntoIterator<Item = StructureElementNode>,
+        ) -> Self {
+            self.children.extend(children);
+            self
+        }
+    }
 
     #[derive(Clone, Debug, Default)]
     pub struct Metadata {
@@ -23,13 +122,36 @@ pub mod pdf {
         pub raster_dpi: Option<scalar>,
         pub pdfa: bool,
         pub encoding_quality: Option<i32>,
-        // TODO: fStructureElementTreeRoot
+        /// The root of the structure element tree, which makes the document a tagged PDF.
+        pub structure_element_tree_root: Option<StructureElementNode>,
     }
 
-    // TODO: SetNodeId
+    /// Associates the drawing commands that follow with the structure element node
+    /// node_id. Has no effect if the canvas does not draw into a PDF document.
+    pub fn set_node_id(canvas: &mut Canvas, node_id: i32) {
+        unsafe { C_SkPDF_SetNodeId(canvas.native_mut(), node_id) }
+    }
 
     pub fn new_document(metadata: Option<&Metadata>) -> Document {
+        let mut sink = sink::Memory::new();
+        let document = make_document(sink.stream_mut(), metadata);
+        Document::new(sink, document)
+    }
+
+    /// Creates a document that writes the PDF into writer while its pages are finished.
+    pub fn new_document_with_writer<'a>(
+        writer: impl io::Write + 'a,
+        metadata: Option<&Metadata>,
+    ) -> Document<state::Open, sink::Writer<'a>> {
+        let mut sink = sink::Writer::new(writer);
+        let document = make_document(sink.stream_mut(), metadata);
+        Document::new(sink, document)
+    }
+
+    fn make_document(stream: &mut SkWStream, metadata: Option<&Metadata>) -> RCHandle<SkDocument> {
         let mut md = InternalMetadata::default();
+        // the native structure element tree must live until the document has copied it.
+        let mut tree = NativeStructureElementTree::default();
         if let Some(metadata) = metadata {
             let internal = md.native_mut();
             internal.fTitle.set_s(&metadata.title);
@@ -51,17 +173,50 @@ pub mod pdf {
             if let Some(encoding_quality) = metadata.encoding_quality {
                 internal.fEncodingQuality = encoding_quality
             }
+            if let Some(root) = &metadata.structure_element_tree_root {
+                internal.fStructureElementTreeRoot = tree.set_root(root);
+            }
         }
 
-        // we can't move the memory stream around anymore as soon it's referred by
-        // the document.
-        let mut memory_stream = Box::pin(DynamicMemoryWStream::new());
-        let document = RCHandle::from_ptr(unsafe {
-            C_SkPDF_MakeDocument(&mut memory_stream.native_mut()._base, md.native())
-        })
-        .unwrap();
+        RCHandle::from_ptr(unsafe { C_SkPDF_MakeDocument(stream, md.native()) }).unwrap()
+    }
+
+    //
+    // Helper for converting the structure element tree into native nodes that refer to their
+    // children.
+    //
+
+    #[derive(Default)]
+    struct NativeStructureElementTree {
+        root: Option<Box<SkPDF_StructureElementNode>>,
+        children: Vec<Vec<SkPDF_StructureElementNode>>,
+    }
+
+    impl NativeStructureElementTree {
+        fn set_root(&mut self, root: &StructureElementNode) -> *const SkPDF_StructureElementNode {
+            let root = Box::new(self.convert(root));
+            let ptr: *const SkPDF_StructureElementNode = root.as_ref();
+            self.root = Some(root);
+            ptr
+        }
 
-        Document::new(memory_stream, document)
+        fn convert(&mut self, node: &StructureElementNode) -> SkPDF_StructureElementNode {
+            let children: Vec<SkPDF_StructureElementNode> =
+                node.children.iter().map(|c| self.convert(c)).collect();
+            let native = SkPDF_StructureElementNode {
+                fChildren: if children.is_empty() {
+                    ptr::null()
+                } else {
+                    children.as_ptr()
+                },
+                fChildCount: children.len(),
+                fNodeId: node.node_id,
+                fType: node.structure_type.into_native(),
+            };
+            // moving the vector does not move its elements.
+            self.children.push(children);
+            native
+        }
     }
 
     //
@@ -91,4 +246,70 @@ pub mod pdf {
             unsafe { self.set2(bytes.as_ptr() as _, bytes.len()) }
         }
     }
+
+    #[test]
+    fn tagged_pdf() {
+        use crate::{Paint, Rect};
+
+        let metadata = Metadata {
+            structure_element_tree_root: Some(
+                StructureElementNode::new(DocumentStructureType::Document, 1).with_children(vec![
+                    StructureElementNode::new(DocumentStructureType::H1, 2),
+                    StructureElementNode::new(DocumentStructureType::P, 3),
+                ]),
+            ),
+            ..Metadata::default()
+        };
+
+        let mut document = new_document(Some(&metadata)).begin_page((100, 100), None);
+        let canvas = document.canvas();
+        set_node_id(canvas, 2);
+        canvas.draw_rect(Rect::from_xywh(10.0, 10.0, 80.0, 10.0), &Paint::default());
+        set_node_id(canvas, 3);
+        canvas.draw_rect(Rect::from_xywh(10.0, 30.0, 80.0, 50.0), &Paint::default());
+        let data = document.end_page().close();
+        let pdf = data.as_bytes();
+        assert!(pdf
+            .windows(b"/StructTreeRoot".len())
+            .any(|w| w == b"/StructTreeRoot"));
+    }
+
+    #[test]
+    fn pdf_to_writer() {
+        use crate::{Paint, Rect};
+
+        let mut pdf = Vec::new();
+        {
+            let mut document =
+                new_document_with_writer(&mut pdf, None).begin_page((100, 100), None);
+            document
+                .canvas()
+                .draw_rect(Rect::from_xywh(10.0, 10.0, 80.0, 80.0), &Paint::default());
+            document.end_page().unwrap().close().unwrap();
+        }
+        assert!(pdf.starts_with(b"%PDF"));
+        assert!(pdf.windows(b"%%EOF".len()).any(|w| w == b"%%EOF"));
+    }
+
+    #[test]
+    fn pdf_to_failing_writer_reports_io_error() {
+        struct FailingWriter;
+
+        impl io::Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let document = new_document_with_writer(FailingWriter, None).begin_page((100, 100), None);
+        let error = match document.end_page() {
+            Ok(document) => document.close().unwrap_err(),
+            Err(error) => error,
+        };
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
 }
@@ -0,0 +1,272 @@
+//! Unicode algorithms provided by ICU.
+
+use skia_bindings::{
+    C_SkLoadICU, C_ubidi_close, C_ubidi_countRuns, C_ubidi_getLevelAt, C_ubidi_getVisualRun,
+    C_ubidi_open, C_ubrk_close, C_ubrk_next, C_ubrk_openLine, UBiDi, UBreakIterator,
+};
+use std::convert::TryInto;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+/// Loads the ICU data the first time it is called and returns `true` if it is available.
+fn load_icu() -> bool {
+    static LOAD: Once = Once::new();
+    static LOADED: AtomicBool = AtomicBool::new(false);
+    LOAD.call_once(|| LOADED.store(unsafe { C_SkLoadICU() }, Ordering::SeqCst));
+    LOADED.load(Ordering::SeqCst)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    fn paragraph_level(direction: Option<Direction>) -> u8 {
+        match direction {
+            Some(Direction::LeftToRight) => 0,
+            Some(Direction::RightToLeft) => 1,
+            // UBIDI_DEFAULT_LTR: derived from the text, left to right if it can't be derived.
+            None => 0xfe,
+        }
+    }
+}
+
+/// A sequence of text with the same embedding level.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BidiRun {
+    /// The byte range of the run in the text.
+    pub range: Range<usize>,
+    /// The bidi embedding level, even levels are left to right, odd levels right to left.
+    pub level: u8,
+}
+
+impl BidiRun {
+    pub fn direction(&self) -> Direction {
+        if self.level % 2 == 0 {
+            Direction::LeftToRight
+        } else {
+            Direction::RightToLeft
+        }
+    }
+}
+
+/// Iterates over the bidi runs of a text in visual order.
+pub struct BidiRuns {
+    native: *mut UBiDi,
+    // referenced by native.
+    _utf16: Vec<u16>,
+    // the byte offsets of the UTF-16 code units, plus the length of the text.
+    utf8_offsets: Vec<usize>,
+    index: usize,
+    count: usize,
+}
+
+impl Drop for BidiRuns {
+    fn drop(&mut self) {
+        unsafe { C_ubidi_close(self.native) }
+    }
+}
+
+impl Iterator for BidiRuns {
+    type Item = BidiRun;
+
+    fn next(&mut self) -> Option<BidiRun> {
+        if self.index >= self.count {
+            return None;
+        }
+        let mut logical_start = 0;
+        let mut length = 0;
+        unsafe {
+            C_ubidi_getVisualRun(
+                self.native,
+                self.index as i32,
+                &mut logical_start,
+                &mut length,
+            )
+        };
+        self.index += 1;
+        let level = unsafe { C_ubidi_getLevelAt(self.native, logical_start) };
+        let start = logical_start as usize;
+        let end = start + length as usize;
+        Some(BidiRun {
+            range: self.utf8_offsets[start]..self.utf8_offsets[end],
+            level,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BidiRuns {}
+
+/// Splits text into runs of the same direction in visual order, that is, in the order
+/// they are displayed from left to right.
+///
+/// If `base_direction` is `None`, the direction of the paragraph is derived from its text.
+/// Returns `None` if the ICU data can not be loaded, the text is too long, or the bidi
+/// algorithm fails.
+pub fn bidi_runs(text: &str, base_direction: impl Into<Option<Direction>>) -> Option<BidiRuns> {
+    if !load_icu() {
+        return None;
+    }
+    let (utf16, utf8_offsets) = to_utf16(text);
+    let native = unsafe {
+        C_ubidi_open(
+            utf16.as_ptr(),
+            utf16.len().try_into().ok()?,
+            Direction::paragraph_level(base_direction.into()),
+        )
+    };
+    if native.is_null() {
+        return None;
+    }
+    let count = unsafe { C_ubidi_countRuns(native) } as usize;
+    Some(BidiRuns {
+        native,
+        _utf16: utf16,
+        utf8_offsets,
+        index: 0,
+        count,
+    })
+}
+
+/// A position in a text at which a line may be broken.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LineBreak {
+    /// The byte offset in the text the next line would start at.
+    pub offset: usize,
+    /// Is this a mandatory break, for example after a newline character?
+    pub is_hard: bool,
+}
+
+/// Iterates over the line break opportunities of a text.
+pub struct LineBreaks {
+    native: *mut UBreakIterator,
+    // referenced by native.
+    _utf16: Vec<u16>,
+    utf8_offsets: Vec<usize>,
+}
+
+impl Drop for LineBreaks {
+    fn drop(&mut self) {
+        unsafe { C_ubrk_close(self.native) }
+    }
+}
+
+impl Iterator for LineBreaks {
+    type Item = LineBreak;
+
+    fn next(&mut self) -> Option<LineBreak> {
+        let mut is_hard = false;
+        let position = unsafe { C_ubrk_next(self.native, &mut is_hard) };
+        if position < 0 {
+            return None;
+        }
+        Some(LineBreak {
+            offset: self.utf8_offsets[position as usize],
+            is_hard,
+        })
+    }
+}
+
+/// Returns the positions at which the lines of a text may be broken according to
+/// the Unicode line breaking algorithm. The end of the text is always a break.
+///
+/// Returns `None` if the ICU data can not be loaded, the text is too long, or the break
+/// iterator can not be created.
+pub fn line_breaks(text: &str) -> Option<LineBreaks> {
+    if !load_icu() {
+        return None;
+    }
+    let (utf16, utf8_offsets) = to_utf16(text);
+    let native = unsafe { C_ubrk_openLine(utf16.as_ptr(), utf16.len().try_into().ok()?) };
+    if native.is_null() {
+        return None;
+    }
+    Some(LineBreaks {
+        native,
+        _utf16: utf16,
+        utf8_offsets,
+    })
+}
+
+/// Converts text to UTF-16 and returns the byte offsets of the UTF-16 code units,
+/// followed by the length of the text.
+fn to_utf16(text: &str) -> (Vec<u16>, Vec<usize>) {
+    let utf16: Vec<u16> = text.encode_utf16().collect();
+    let mut utf8_offsets = Vec::with_capacity(utf16.len() + 1);
+    for (offset, c) in text.char_indices() {
+        for _ in 0..c.len_utf16() {
+            utf8_offsets.push(offset);
+        }
+    }
+    utf8_offsets.push(text.len());
+    (utf16, utf8_offsets)
+}
+
+#[test]
+fn left_to_right_text_is_a_single_run() {
+    let runs: Vec<_> = bidi_runs("Hello", None).unwrap().collect();
+    assert_eq!(
+        runs,
+        vec![BidiRun {
+            range: 0..5,
+            level: 0
+        }]
+    );
+}
+
+#[test]
+fn mixed_text_runs_are_in_visual_order() {
+    // the Hebrew word "shalom" embedded in left to right text.
+    let text = "a \u{05e9}\u{05dc}\u{05d5}\u{05dd} b";
+    let runs: Vec<_> = bidi_runs(text, Direction::LeftToRight).unwrap().collect();
+    let directions: Vec<_> = runs.iter().map(|run| run.direction()).collect();
+    assert_eq!(
+        directions,
+        vec![
+            Direction::LeftToRight,
+            Direction::RightToLeft,
+            Direction::LeftToRight
+        ]
+    );
+    assert_eq!(&text[runs[0].range.clone()], "a ");
+    assert_eq!(
+        &text[runs[1].range.clone()],
+        "\u{05e9}\u{05dc}\u{05d5}\u{05dd}"
+    );
+    assert_eq!(&text[runs[2].range.clone()], " b");
+}
+
+#[test]
+fn empty_text_has_no_runs() {
+    assert_eq!(bidi_runs("", None).unwrap().count(), 0);
+}
+
+#[test]
+fn line_break_opportunities() {
+    let breaks: Vec<_> = line_breaks("Hello World\nBye").unwrap().collect();
+    assert_eq!(
+        breaks,
+        vec![
+            LineBreak {
+                offset: 6,
+                is_hard: false
+            },
+            LineBreak {
+                offset: 12,
+                is_hard: true
+            },
+            LineBreak {
+                offset: 15,
+                is_hard: false
+            },
+        ]
+    );
+}
@@ -6,13 +6,15 @@
 use crate::prelude::*;
 use crate::Data;
 use skia_bindings::{
-    C_SkDynamicMemoryWStream_Construct, C_SkDynamicMemoryWStream_detachAsData,
-    C_SkDynamicMemoryWStream_detachAsStream, C_SkMemoryStream_MakeDirect, C_SkStream_delete,
-    C_SkWStream_destruct, C_SkWStream_write, SkDynamicMemoryWStream, SkMemoryStream, SkStream,
-    SkStreamAsset,
+    C_RustStream_new, C_RustWStream_delete, C_RustWStream_new, C_SkDynamicMemoryWStream_Construct,
+    C_SkDynamicMemoryWStream_detachAsData, C_SkDynamicMemoryWStream_detachAsStream,
+    C_SkMemoryStream_MakeDirect, C_SkStream_delete, C_SkWStream_destruct, C_SkWStream_write,
+    SkDynamicMemoryWStream, SkMemoryStream, SkStream, SkStreamAsset, SkWStream,
 };
+use std::ffi::c_void;
 use std::marker::PhantomData;
-use std::ptr;
+use std::panic::{self, AssertUnwindSafe};
+use std::{io, mem, ptr, slice};
 
 /// Trait representing an Skia allocated Stream type with a base class of SkStream.
 pub struct Stream<N: NativeStreamBase>(*mut N);
@@ -139,3 +141,315 @@ fn memory_stream_from_bytes() {
     let stream = MemoryStream::from_bytes(&[1, 2, 3]);
     drop(stream);
 }
+
+/// A Skia stream that reads from a Rust reader.
+///
+/// Reading errors end the stream, the io::Error that caused it can be retrieved with
+/// take_error().
+pub struct RustStream<'a> {
+    native: *mut SkStreamAsset,
+    length: Option<usize>,
+    // owned by the native stream, which drops it when it is deleted.
+    reader: *mut RustStreamReader<'a>,
+}
+
+trait ReadSeek: io::Read + io::Seek {}
+impl<T: io::Read + io::Seek> ReadSeek for T {}
+
+enum Reader<'a> {
+    Read(Box<dyn io::Read + 'a>),
+    ReadSeek(Box<dyn ReadSeek + 'a>),
+}
+
+struct RustStreamReader<'a> {
+    reader: Reader<'a>,
+    // the position of the reader the stream started at.
+    start: u64,
+    error: Option<io::Error>,
+}
+
+impl<'a> Drop for RustStream<'a> {
+    fn drop(&mut self) {
+        unsafe { C_SkStream_delete(self.native as _) }
+    }
+}
+
+impl<'a> RustStream<'a> {
+    /// Creates a stream that can only be read sequentially.
+    pub fn new(reader: impl io::Read + 'a) -> Self {
+        Self::from_reader(Reader::Read(Box::new(reader)), 0, None)
+    }
+
+    /// Creates a seekable stream with a known length, starting at the current position
+    /// of the reader.
+    pub fn new_seekable(mut reader: impl io::Read + io::Seek + 'a) -> io::Result<Self> {
+        let start = reader.seek(io::SeekFrom::Current(0))?;
+        let end = reader.seek(io::SeekFrom::End(0))?;
+        reader.seek(io::SeekFrom::Start(start))?;
+        let length = end.saturating_sub(start).try_into().unwrap();
+        Ok(Self::from_reader(
+            Reader::ReadSeek(Box::new(reader)),
+            start,
+            Some(length),
+        ))
+    }
+
+    fn from_reader(reader: Reader<'a>, start: u64, length: Option<usize>) -> Self {
+        let reader = Box::into_raw(Box::new(RustStreamReader {
+            reader,
+            start,
+            error: None,
+        }));
+
+        unsafe extern "C" fn read_trampoline(
+            reader: *mut c_void,
+            buffer: *mut c_void,
+            size: usize,
+        ) -> usize {
+            let reader = &mut *(reader as *mut RustStreamReader);
+            if reader.error.is_some() {
+                return 0;
+            }
+            let buffer = slice::from_raw_parts_mut(buffer as *mut u8, size);
+            loop {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| match &mut reader.reader {
+                    Reader::Read(r) => r.read(buffer),
+                    Reader::ReadSeek(r) => r.read(buffer),
+                }))
+                .unwrap_or_else(|_| Err(panicked("reader")));
+                match result {
+                    Ok(read) => return read,
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        reader.error = Some(e);
+                        return 0;
+                    }
+                }
+            }
+        }
+
+        unsafe extern "C" fn seek_trampoline(reader: *mut c_void, position: usize) -> bool {
+            let reader = &mut *(reader as *mut RustStreamReader);
+            let position = reader.start + position as u64;
+            let result = match &mut reader.reader {
+                Reader::ReadSeek(r) => {
+                    panic::catch_unwind(AssertUnwindSafe(|| r.seek(io::SeekFrom::Start(position))))
+                        .unwrap_or_else(|_| Err(panicked("reader")))
+                }
+                Reader::Read(_) => return false,
+            };
+            match result {
+                Ok(_) => true,
+                Err(e) => {
+                    reader.error = Some(e);
+                    false
+                }
+            }
+        }
+
+        unsafe extern "C" fn drop_trampoline(reader: *mut c_void) {
+            let reader = Box::from_raw(reader as *mut RustStreamReader);
+            // there is no one left to report a panic of the reader's drop to.
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(reader)));
+        }
+
+        let native = unsafe {
+            C_RustStream_new(
+                reader as _,
+                length.unwrap_or_default(),
+                Some(read_trampoline),
+                if length.is_some() {
+                    Some(seek_trampoline)
+                } else {
+                    None
+                },
+                Some(drop_trampoline),
+            )
+        };
+
+        RustStream {
+            native,
+            length,
+            reader,
+        }
+    }
+
+    pub fn stream_mut(&mut self) -> &mut SkStream {
+        unsafe { (*self.native).as_stream_mut() }
+    }
+
+    /// Returns the length of the stream if it was created with new_seekable().
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// Returns the first error the reader returned and resets the error state.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        unsafe { (*self.reader).error.take() }
+    }
+}
+
+impl RustStream<'static> {
+    /// Releases the native stream, which keeps the reader until it is deleted.
+    ///
+    /// For functions that take ownership of the stream.
+    pub fn into_native(self) -> *mut SkStreamAsset {
+        let native = self.native;
+        mem::forget(self);
+        native
+    }
+}
+
+/// A Skia write stream that forwards everything written to a Rust writer.
+///
+/// Skia only sees a failing write, the io::Error that caused it is kept and can be
+/// retrieved with take_error().
+pub struct RustWStream<'a> {
+    native: *mut SkWStream,
+    // boxed, because Skia keeps a pointer to it.
+    writer: Box<RustWStreamWriter<'a>>,
+}
+
+struct RustWStreamWriter<'a> {
+    writer: Box<dyn io::Write + 'a>,
+    error: Option<io::Error>,
+}
+
+impl<'a> Drop for RustWStream<'a> {
+    fn drop(&mut self) {
+        unsafe { C_RustWStream_delete(self.native) }
+    }
+}
+
+impl<'a> RustWStream<'a> {
+    pub fn new(writer: impl io::Write + 'a) -> Self {
+        let mut writer = Box::new(RustWStreamWriter {
+            writer: Box::new(writer),
+            error: None,
+        });
+
+        unsafe extern "C" fn write_trampoline(
+            writer: *mut c_void,
+            buffer: *const c_void,
+            size: usize,
+        ) -> bool {
+            let writer = &mut *(writer as *mut RustWStreamWriter);
+            if writer.error.is_some() {
+                return false;
+            }
+            if size == 0 {
+                return true;
+            }
+            let bytes = slice::from_raw_parts(buffer as *const u8, size);
+            let result = panic::catch_unwind(AssertUnwindSafe(|| writer.writer.write_all(bytes)))
+                .unwrap_or_else(|_| Err(panicked("writer")));
+            match result {
+                Ok(()) => true,
+                Err(e) => {
+                    writer.error = Some(e);
+                    false
+                }
+            }
+        }
+
+        unsafe extern "C" fn flush_trampoline(writer: *mut c_void) {
+            let writer = &mut *(writer as *mut RustWStreamWriter);
+            if writer.error.is_none() {
+                writer.error = panic::catch_unwind(AssertUnwindSafe(|| writer.writer.flush()))
+                    .unwrap_or_else(|_| Err(panicked("writer")))
+                    .err();
+            }
+        }
+
+        let native = unsafe {
+            C_RustWStream_new(
+                writer.as_mut() as *mut RustWStreamWriter as _,
+                Some(write_trampoline),
+                Some(flush_trampoline),
+            )
+        };
+
+        RustWStream { native, writer }
+    }
+
+    pub fn stream_mut(&mut self) -> &mut SkWStream {
+        unsafe { &mut *self.native }
+    }
+
+    /// Flushes the writer, unless writing failed before.
+    pub fn flush(&mut self) {
+        if self.writer.error.is_none() {
+            self.writer.error = self.writer.writer.flush().err();
+        }
+    }
+
+    /// Returns the first error the writer returned and resets the error state.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.writer.error.take()
+    }
+}
+
+// Panics must not unwind into Skia, so they end the stream with this error.
+fn panicked(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("the {} panicked", what))
+}
+
+#[test]
+fn rust_wstream_forwards_writes() {
+    let mut written = Vec::new();
+    {
+        let mut stream = RustWStream::new(&mut written);
+        assert!(unsafe { C_SkWStream_write(stream.stream_mut(), b"skia".as_ptr() as _, 4) });
+        assert!(stream.take_error().is_none());
+    }
+    assert_eq!(written, b"skia");
+}
+
+#[test]
+fn rust_wstream_reports_panics_as_errors() {
+    struct PanickingWriter;
+
+    impl io::Write for PanickingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            panic!("write")
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut stream = RustWStream::new(PanickingWriter);
+    assert!(!unsafe { C_SkWStream_write(stream.stream_mut(), b"skia".as_ptr() as _, 4) });
+    assert_eq!(
+        stream.take_error().map(|e| e.kind()),
+        Some(io::ErrorKind::Other)
+    );
+}
+
+#[test]
+fn rust_stream_starting_past_the_end_is_empty() {
+    let mut cursor = io::Cursor::new(vec![1u8, 2, 3]);
+    cursor.set_position(5);
+    let stream = RustStream::new_seekable(cursor).unwrap();
+    assert_eq!(stream.length(), Some(0));
+}
+
+#[test]
+fn rust_stream_reports_panics_as_errors() {
+    struct PanickingReader;
+
+    impl io::Read for PanickingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("read")
+        }
+    }
+
+    let mut stream = RustStream::new(PanickingReader);
+    let data = unsafe { skia_bindings::C_SkData_MakeFromStream(stream.stream_mut(), 4) };
+    assert!(data.is_null());
+    assert_eq!(
+        stream.take_error().map(|e| e.kind()),
+        Some(io::ErrorKind::Other)
+    );
+}
@@ -3,9 +3,17 @@ mod core;
 mod docs;
 mod effects;
 pub mod gpu;
+#[cfg(feature = "icu")]
+pub mod icu;
 mod interop;
+#[cfg(feature = "particles")]
+pub mod particles;
 mod pathops;
 mod prelude;
+#[cfg(feature = "shaper")]
+pub mod shaper;
+#[cfg(feature = "animation")]
+pub mod skottie;
 #[cfg(feature = "svg")]
 pub mod svg;
 // TODO: We don't export utils/* into the crate's root yet. Should we?
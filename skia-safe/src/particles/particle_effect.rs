@@ -0,0 +1,142 @@
+use crate::prelude::*;
+use crate::Canvas;
+use skia_bindings::{
+    C_SkParticleEffectParams_MakeFromJSON, C_SkParticleEffectParams_ref,
+    C_SkParticleEffectParams_unique, C_SkParticleEffectParams_unref, C_SkParticleEffect_Make,
+    C_SkParticleEffect_draw, C_SkParticleEffect_isAlive, C_SkParticleEffect_ref,
+    C_SkParticleEffect_setParams, C_SkParticleEffect_start, C_SkParticleEffect_unique,
+    C_SkParticleEffect_unref, C_SkParticleEffect_update, SkParticleEffect, SkParticleEffectParams,
+};
+
+/// The description of a particle effect, loaded from JSON.
+pub type ParticleEffectParams = RCHandle<SkParticleEffectParams>;
+
+impl NativeRefCounted for SkParticleEffectParams {
+    fn _ref(&self) {
+        unsafe { C_SkParticleEffectParams_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { C_SkParticleEffectParams_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { C_SkParticleEffectParams_unique(self) }
+    }
+}
+
+impl RCHandle<SkParticleEffectParams> {
+    pub fn from_json(json: &[u8]) -> Option<ParticleEffectParams> {
+        ParticleEffectParams::from_ptr(unsafe {
+            C_SkParticleEffectParams_MakeFromJSON(json.as_ptr() as _, json.len())
+        })
+    }
+}
+
+/// A running particle effect.
+pub type ParticleEffect = RCHandle<SkParticleEffect>;
+
+impl NativeRefCounted for SkParticleEffect {
+    fn _ref(&self) {
+        unsafe { C_SkParticleEffect_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { C_SkParticleEffect_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { C_SkParticleEffect_unique(self) }
+    }
+}
+
+impl RCHandle<SkParticleEffect> {
+    /// Creates an effect, effects created from the same parameters and seed
+    /// behave identically.
+    pub fn new(params: &ParticleEffectParams, seed: u32) -> ParticleEffect {
+        ParticleEffect::from_ptr(unsafe { C_SkParticleEffect_Make(params.shared_native(), seed) })
+            .unwrap()
+    }
+
+    /// Starts spawning particles, `now` is the time in seconds.
+    pub fn start(&mut self, now: f64, looping: bool) -> &mut Self {
+        unsafe { C_SkParticleEffect_start(self.native_mut(), now, looping) }
+        self
+    }
+
+    /// Advances the simulation to `now`, the time in seconds.
+    pub fn update(&mut self, now: f64) -> &mut Self {
+        unsafe { C_SkParticleEffect_update(self.native_mut(), now) }
+        self
+    }
+
+    pub fn draw(&mut self, mut canvas: impl AsMut<Canvas>) {
+        unsafe { C_SkParticleEffect_draw(self.native_mut(), canvas.as_mut().native_mut()) }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        unsafe { C_SkParticleEffect_isAlive(self.native()) }
+    }
+
+    pub fn set_params(&mut self, params: &ParticleEffectParams) -> &mut Self {
+        unsafe { C_SkParticleEffect_setParams(self.native_mut(), params.shared_native()) }
+        self
+    }
+}
+
+#[cfg(test)]
+const TEST_EFFECT: &str = r#"{
+    "MaxCount": 32,
+    "Duration": 1,
+    "Rate": 32,
+    "Drawable": { "Type": "SkCircleDrawable", "Radius": 4 },
+    "Spawn": [{
+        "Type": "SkLinearVelocityAffector",
+        "Enabled": true,
+        "Force": true,
+        "Frame": "World",
+        "Angle": { "XValues": [], "Segments": [{ "Type": "Constant", "Ranged": true, "A0": 0, "A1": 360 }] },
+        "Strength": { "XValues": [], "Segments": [{ "Type": "Constant", "Ranged": true, "A0": 10, "A1": 50 }] }
+    }]
+}"#;
+
+#[test]
+fn test_invalid_params() {
+    assert!(ParticleEffectParams::from_json(b"[]").is_none());
+}
+
+#[test]
+fn test_effect_lifecycle() {
+    let params = ParticleEffectParams::from_json(TEST_EFFECT.as_bytes()).unwrap();
+    let mut effect = ParticleEffect::new(&params, 1);
+    assert!(!effect.is_alive());
+    effect.start(0.0, false);
+    assert!(effect.is_alive());
+    effect.update(0.5);
+    assert!(effect.is_alive());
+}
+
+#[test]
+fn test_the_seed_determines_the_drawing() {
+    use crate::{EncodedImageFormat, Surface};
+
+    let params = ParticleEffectParams::from_json(TEST_EFFECT.as_bytes()).unwrap();
+    let draw = |seed: Option<u32>| {
+        let mut surface = Surface::new_raster_n32_premul((64, 64)).unwrap();
+        if let Some(seed) = seed {
+            let mut effect = ParticleEffect::new(&params, seed);
+            effect.start(0.0, false).update(0.5);
+            surface.canvas().translate((32, 32));
+            effect.draw(surface.canvas());
+        }
+        surface
+            .image_snapshot()
+            .encode_to_data(EncodedImageFormat::PNG)
+            .unwrap()
+    };
+
+    let drawing = draw(Some(7));
+    assert!(drawing != draw(None));
+    assert!(drawing == draw(Some(7)));
+    assert!(drawing != draw(Some(8)));
+}
@@ -750,3 +750,14 @@ impl<T: Sized> BorrowsFrom for T {
         Borrows(self, PhantomData)
     }
 }
+
+//
+// Rust functions that are called from Skia.
+//
+
+/// Calls the body `f` of a function that is called from Skia and returns `neutral` instead
+/// if it panics, because unwinding into C++ is undefined behavior. The panic is still reported
+/// by the panic hook.
+pub(crate) fn catch_panic_or<R>(neutral: R, f: impl FnOnce() -> R) -> R {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or(neutral)
+}
@@ -0,0 +1,303 @@
+//! Text shaping with HarfBuzz.
+
+use crate::prelude::*;
+use crate::{scalar, Font, GlyphId, Point, TextBlob, Vector};
+use skia_bindings::{
+    C_RustRunHandler_new, C_SkShaper_Make, C_SkShaper_MakeHarfBuzz, C_SkShaper_MakePrimitive,
+    C_SkShaper_RunHandler_delete, C_SkShaper_delete, C_SkShaper_shape,
+    C_SkTextBlobBuilderRunHandler_makeBlob, C_SkTextBlobBuilderRunHandler_new, SkFont, SkPoint,
+    SkShaper, SkShaper_RunHandler, SkVector,
+};
+use std::any::Any;
+use std::ffi::c_void;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+/// Converts text into positioned glyphs and breaks it into lines.
+pub struct Shaper(*mut SkShaper);
+
+impl NativeAccess<SkShaper> for Shaper {
+    fn native(&self) -> &SkShaper {
+        unsafe { &*self.0 }
+    }
+
+    fn native_mut(&mut self) -> &mut SkShaper {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl Drop for Shaper {
+    fn drop(&mut self) {
+        unsafe { C_SkShaper_delete(self.0) }
+    }
+}
+
+impl Default for Shaper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shaper {
+    /// Creates the best shaper available, which is the HarfBuzz shaper.
+    pub fn new() -> Shaper {
+        Shaper(unsafe { C_SkShaper_Make() })
+    }
+
+    /// Creates a shaper that maps characters to glyphs one-to-one.
+    pub fn new_primitive() -> Shaper {
+        Shaper(unsafe { C_SkShaper_MakePrimitive() })
+    }
+
+    pub fn new_harfbuzz() -> Option<Shaper> {
+        unsafe { C_SkShaper_MakeHarfBuzz() }.to_option().map(Shaper)
+    }
+
+    /// Shapes `text` into lines of at most `width` and returns the resulting text blob
+    /// and the point below the last line.
+    ///
+    /// Returns `None` if the text does not produce any glyphs.
+    pub fn shape(
+        &self,
+        text: &str,
+        font: &Font,
+        left_to_right: bool,
+        width: scalar,
+    ) -> Option<(TextBlob, Point)> {
+        let handler =
+            NativeRunHandler(unsafe { C_SkTextBlobBuilderRunHandler_new(text.as_ptr() as _) });
+        let end_point = self.shape_native(
+            text,
+            font,
+            left_to_right,
+            width,
+            Point::default(),
+            handler.0,
+        );
+        TextBlob::from_ptr(unsafe { C_SkTextBlobBuilderRunHandler_makeBlob(handler.0) })
+            .map(|blob| (blob, end_point))
+    }
+
+    /// Shapes `text` into lines of at most `width`, starting at `offset` and passes the
+    /// resulting runs to `handler`. Returns the point below the last line.
+    pub fn shape_with_handler(
+        &self,
+        text: &str,
+        font: &Font,
+        left_to_right: bool,
+        width: scalar,
+        offset: impl Into<Point>,
+        handler: &mut dyn RunHandler,
+    ) -> Point {
+        let mut context = RunHandlerContext {
+            handler,
+            panic: None,
+        };
+        let native_handler = NativeRunHandler(unsafe {
+            C_RustRunHandler_new(
+                &mut context as *mut RunHandlerContext as *mut c_void,
+                text.as_ptr() as _,
+                Some(commit_run),
+                Some(commit_line),
+            )
+        });
+        let end_point = self.shape_native(
+            text,
+            font,
+            left_to_right,
+            width,
+            offset.into(),
+            native_handler.0,
+        );
+        if let Some(panic) = context.panic {
+            panic::resume_unwind(panic)
+        }
+        end_point
+    }
+
+    fn shape_native(
+        &self,
+        text: &str,
+        font: &Font,
+        left_to_right: bool,
+        width: scalar,
+        offset: Point,
+        handler: *mut SkShaper_RunHandler,
+    ) -> Point {
+        let mut end_point = Point::default();
+        unsafe {
+            C_SkShaper_shape(
+                self.native(),
+                handler,
+                font.native(),
+                text.as_ptr() as _,
+                text.len(),
+                left_to_right,
+                offset.native(),
+                width,
+                end_point.native_mut(),
+            )
+        }
+        end_point
+    }
+}
+
+struct NativeRunHandler(*mut SkShaper_RunHandler);
+
+impl Drop for NativeRunHandler {
+    fn drop(&mut self) {
+        unsafe { C_SkShaper_RunHandler_delete(self.0) }
+    }
+}
+
+/// A shaped run of glyphs that share the same font.
+pub struct Run<'a> {
+    pub font: &'a Font,
+    pub advance: Vector,
+    pub ascent: scalar,
+    pub descent: scalar,
+    pub leading: scalar,
+    /// The byte range of the text the run was shaped from.
+    pub utf8_range: Range<usize>,
+    pub glyphs: &'a [GlyphId],
+    pub positions: &'a [Point],
+    /// The byte offsets into the text of the clusters the glyphs belong to.
+    pub clusters: &'a [u32],
+}
+
+/// Receives the result of Shaper::shape_with_handler().
+pub trait RunHandler {
+    fn commit_run(&mut self, run: &Run);
+
+    /// Called after all the runs of a line were committed.
+    fn commit_line(&mut self) {}
+}
+
+struct RunHandlerContext<'a> {
+    handler: &'a mut dyn RunHandler,
+    // panics must not unwind into Skia, so they are resumed after shaping.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl RunHandlerContext<'_> {
+    fn call(&mut self, f: impl FnOnce(&mut dyn RunHandler)) {
+        if self.panic.is_none() {
+            let handler = &mut *self.handler;
+            self.panic = panic::catch_unwind(AssertUnwindSafe(|| f(handler))).err();
+        }
+    }
+}
+
+unsafe extern "C" fn commit_run(
+    context: *mut c_void,
+    font: *const SkFont,
+    advance: *const SkVector,
+    ascent: scalar,
+    descent: scalar,
+    leading: scalar,
+    utf8_begin: usize,
+    utf8_end: usize,
+    glyph_count: usize,
+    glyphs: *const GlyphId,
+    positions: *const SkPoint,
+    clusters: *const u32,
+) {
+    let context = &mut *(context as *mut RunHandlerContext);
+    // the glyph arrays may be null if the run is empty.
+    let (glyphs, positions, clusters): (&[GlyphId], &[Point], &[u32]) = if glyph_count == 0 {
+        (&[], &[], &[])
+    } else {
+        (
+            slice::from_raw_parts(glyphs, glyph_count),
+            slice::from_raw_parts(positions as *const Point, glyph_count),
+            slice::from_raw_parts(clusters, glyph_count),
+        )
+    };
+    let run = Run {
+        font: Font::from_native_ref(&*font),
+        advance: Vector::from_native(*advance),
+        ascent,
+        descent,
+        leading,
+        utf8_range: utf8_begin..utf8_end,
+        glyphs,
+        positions,
+        clusters,
+    };
+    context.call(|handler| handler.commit_run(&run))
+}
+
+unsafe extern "C" fn commit_line(context: *mut c_void) {
+    let context = &mut *(context as *mut RunHandlerContext);
+    context.call(|handler| handler.commit_line())
+}
+
+#[test]
+fn shape_to_text_blob() {
+    let shaper = Shaper::new();
+    let (_, end_point) = shaper
+        .shape("Hello, World", &Font::default(), true, 1000.0)
+        .unwrap();
+    assert!(end_point.y > 0.0);
+}
+
+#[test]
+fn shape_empty_text() {
+    assert!(Shaper::new()
+        .shape("", &Font::default(), true, 1000.0)
+        .is_none());
+}
+
+#[test]
+fn shape_with_run_handler() {
+    #[derive(Default)]
+    struct Handler {
+        glyphs: Vec<GlyphId>,
+        clusters: Vec<u32>,
+        lines: usize,
+    }
+
+    impl RunHandler for Handler {
+        fn commit_run(&mut self, run: &Run) {
+            assert_eq!(run.glyphs.len(), run.positions.len());
+            self.glyphs.extend_from_slice(run.glyphs);
+            self.clusters.extend_from_slice(run.clusters);
+        }
+
+        fn commit_line(&mut self) {
+            self.lines += 1;
+        }
+    }
+
+    let text = "Hello";
+    let mut handler = Handler::default();
+    Shaper::new().shape_with_handler(text, &Font::default(), true, 1000.0, (0, 0), &mut handler);
+    assert_eq!(handler.lines, 1);
+    assert_eq!(handler.glyphs.len(), text.len());
+    assert!(handler
+        .clusters
+        .iter()
+        .all(|&cluster| (cluster as usize) < text.len()));
+}
+
+#[test]
+#[should_panic(expected = "commit_run")]
+fn shape_with_handler_resumes_panics_of_the_handler() {
+    struct Handler;
+
+    impl RunHandler for Handler {
+        fn commit_run(&mut self, _run: &Run) {
+            panic!("commit_run")
+        }
+    }
+
+    Shaper::new().shape_with_handler(
+        "Hello",
+        &Font::default(),
+        true,
+        1000.0,
+        (0, 0),
+        &mut Handler,
+    );
+}
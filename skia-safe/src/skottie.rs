@@ -0,0 +1,8 @@
+mod animation;
+pub use self::animation::*;
+
+mod property;
+pub use self::property::*;
+
+mod resource_provider;
+pub use self::resource_provider::*;
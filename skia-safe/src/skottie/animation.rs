@@ -0,0 +1,327 @@
+use crate::prelude::*;
+use crate::skottie::{property, resource_provider, PropertyObserver, ResourceProvider};
+use crate::{scalar, Canvas, FontMgr, Rect, Size};
+use skia_bindings::{
+    skottie_Animation, C_skottie_Animation_Builder_make, C_skottie_Animation_Make,
+    C_skottie_Animation_duration, C_skottie_Animation_ref, C_skottie_Animation_render,
+    C_skottie_Animation_seek, C_skottie_Animation_size, C_skottie_Animation_unique,
+    C_skottie_Animation_unref, C_skottie_ReadAnimationTiming,
+};
+use std::path::Path;
+use std::{fs, io, ptr};
+
+impl NativeRefCounted for skottie_Animation {
+    fn _ref(&self) {
+        unsafe { C_skottie_Animation_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { C_skottie_Animation_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { C_skottie_Animation_unique(self) }
+    }
+}
+
+/// A Lottie animation loaded by Skottie.
+pub struct Animation {
+    native: RCHandle<skottie_Animation>,
+    fps: scalar,
+    in_point: scalar,
+    out_point: scalar,
+}
+
+impl NativeAccess<skottie_Animation> for Animation {
+    fn native(&self) -> &skottie_Animation {
+        self.native.native()
+    }
+
+    fn native_mut(&mut self) -> &mut skottie_Animation {
+        self.native.native_mut()
+    }
+}
+
+impl Animation {
+    /// Loads an animation from its JSON representation.
+    pub fn from_bytes(json: &[u8]) -> Option<Animation> {
+        Self::from_ptr(
+            unsafe { C_skottie_Animation_Make(json.as_ptr() as _, json.len()) },
+            json,
+        )
+    }
+
+    /// Loads an animation from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Animation> {
+        let json = fs::read(path)?;
+        Self::from_bytes(&json).ok_or_else(invalid_animation)
+    }
+
+    fn from_ptr(ptr: *mut skottie_Animation, json: &[u8]) -> Option<Animation> {
+        let native = RCHandle::from_ptr(ptr)?;
+
+        let mut fps = 0.0;
+        let mut in_point = 0.0;
+        let mut out_point = 0.0;
+        unsafe {
+            C_skottie_ReadAnimationTiming(
+                json.as_ptr() as _,
+                json.len(),
+                &mut fps,
+                &mut in_point,
+                &mut out_point,
+            )
+        }
+        .if_true_some(Animation {
+            native,
+            fps,
+            in_point,
+            out_point,
+        })
+    }
+
+    /// The duration of the animation in seconds.
+    pub fn duration(&self) -> scalar {
+        unsafe { C_skottie_Animation_duration(self.native()) }
+    }
+
+    /// The frame rate of the animation in frames per second.
+    pub fn fps(&self) -> scalar {
+        self.fps
+    }
+
+    /// The frame the animation starts at.
+    pub fn in_point(&self) -> scalar {
+        self.in_point
+    }
+
+    /// The frame the animation ends at.
+    pub fn out_point(&self) -> scalar {
+        self.out_point
+    }
+
+    pub fn size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { C_skottie_Animation_size(self.native(), size.native_mut()) }
+        size
+    }
+
+    /// Updates the animation state for `t`, a normalized frame selector in the
+    /// range \[0..1\] (0 selects the first frame, 1 the last one).
+    pub fn seek(&mut self, t: scalar) -> &mut Self {
+        unsafe { C_skottie_Animation_seek(self.native_mut(), t) }
+        self
+    }
+
+    /// Updates the animation state for the frame index `frame`, which is relative to the
+    /// animation's in point.
+    pub fn seek_frame(&mut self, frame: scalar) -> &mut Self {
+        let frames = self.out_point - self.in_point;
+        let t = if frames > 0.0 { frame / frames } else { 0.0 };
+        self.seek(t)
+    }
+
+    /// Draws the current animation frame into `dst`, or into a rectangle of the animation's
+    /// size at the origin if `dst` is `None`.
+    pub fn render(&self, mut canvas: impl AsMut<Canvas>, dst: Option<&Rect>) {
+        unsafe {
+            C_skottie_Animation_render(
+                self.native(),
+                canvas.as_mut().native_mut(),
+                dst.native_ptr_or_null(),
+            )
+        }
+    }
+}
+
+/// Builds animations that load their resources from a ResourceProvider and
+/// expose their properties to a PropertyObserver.
+#[derive(Default)]
+pub struct AnimationBuilder<'a> {
+    resource_provider: Option<&'a dyn ResourceProvider>,
+    property_observer: Option<&'a mut dyn PropertyObserver>,
+    font_mgr: Option<FontMgr>,
+}
+
+impl<'a> AnimationBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_resource_provider(&mut self, provider: &'a dyn ResourceProvider) -> &mut Self {
+        self.resource_provider = Some(provider);
+        self
+    }
+
+    pub fn set_property_observer(&mut self, observer: &'a mut dyn PropertyObserver) -> &mut Self {
+        self.property_observer = Some(observer);
+        self
+    }
+
+    pub fn set_font_manager(&mut self, font_mgr: FontMgr) -> &mut Self {
+        self.font_mgr = Some(font_mgr);
+        self
+    }
+
+    /// Builds an animation from its JSON representation.
+    /// The resource provider and the property observer are called only while the
+    /// animation is built.
+    pub fn make(&mut self, json: &[u8]) -> Option<Animation> {
+        let resource_provider = self
+            .resource_provider
+            .as_ref()
+            .map_or(ptr::null_mut(), resource_provider::new_native);
+        let property_observer = self
+            .property_observer
+            .as_mut()
+            .map_or(ptr::null_mut(), property::new_native);
+
+        Animation::from_ptr(
+            unsafe {
+                C_skottie_Animation_Builder_make(
+                    json.as_ptr() as _,
+                    json.len(),
+                    resource_provider,
+                    property_observer,
+                    self.font_mgr.shared_ptr(),
+                )
+            },
+            json,
+        )
+    }
+
+    /// Builds an animation from a JSON file.
+    pub fn make_from_file(&mut self, path: impl AsRef<Path>) -> io::Result<Animation> {
+        let json = fs::read(path)?;
+        self.make(&json).ok_or_else(invalid_animation)
+    }
+}
+
+fn invalid_animation() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "failed to load the animation")
+}
+
+#[cfg(test)]
+const TEST_ANIMATION: &str = r##"{
+    "v": "5.5.2", "fr": 30, "ip": 0, "op": 60, "w": 100, "h": 50,
+    "layers": [{
+        "ty": 1, "ind": 1, "nm": "solid", "ip": 0, "op": 60, "st": 0,
+        "sw": 100, "sh": 50, "sc": "#ff0000",
+        "ks": {
+            "o": { "a": 0, "k": 100 },
+            "r": { "a": 0, "k": 0 },
+            "p": { "a": 0, "k": [50, 25, 0] },
+            "a": { "a": 0, "k": [50, 25, 0] },
+            "s": { "a": 0, "k": [100, 100, 100] }
+        }
+    }]
+}"##;
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_animation_properties() {
+    let animation = Animation::from_bytes(TEST_ANIMATION.as_bytes()).unwrap();
+    assert_eq!(animation.size(), Size::new(100.0, 50.0));
+    assert_eq!(animation.fps(), 30.0);
+    assert_eq!(animation.in_point(), 0.0);
+    assert_eq!(animation.out_point(), 60.0);
+    assert_eq!(animation.duration(), 2.0);
+}
+
+#[test]
+fn test_invalid_animation() {
+    assert!(Animation::from_bytes(b"{").is_none());
+}
+
+#[test]
+fn test_animation_render() {
+    use crate::{Bitmap, Color, Surface};
+
+    let mut animation = Animation::from_bytes(TEST_ANIMATION.as_bytes()).unwrap();
+    animation.seek_frame(30.0);
+    let mut surface = Surface::new_raster_n32_premul((200, 100)).unwrap();
+    animation.render(surface.canvas(), Some(&Rect::from_size((200, 100))));
+    let mut bitmap = Bitmap::new();
+    bitmap.alloc_n32_pixels((200, 100), None);
+    assert!(surface.read_pixels_to_bitmap(&bitmap, (0, 0)));
+    assert_eq!(bitmap.get_color((150, 75)), Color::RED);
+}
+
+#[test]
+fn test_property_observer() {
+    use crate::skottie::ColorPropertyHandle;
+    use crate::{Bitmap, Color, Surface};
+
+    #[derive(Default)]
+    struct Observer {
+        colors: Vec<(String, ColorPropertyHandle)>,
+    }
+
+    impl PropertyObserver for Observer {
+        fn on_color_property(&mut self, node_name: &str, handle: ColorPropertyHandle) {
+            self.colors.push((node_name.into(), handle))
+        }
+    }
+
+    let mut observer = Observer::default();
+    let animation = AnimationBuilder::new()
+        .set_property_observer(&mut observer)
+        .make(TEST_ANIMATION.as_bytes())
+        .unwrap();
+
+    let (name, color) = &mut observer.colors[0];
+    assert_eq!(name.as_str(), "solid");
+    assert_eq!(color.get(), Color::RED);
+    color.set(Color::GREEN);
+
+    let mut surface = Surface::new_raster_n32_premul((100, 50)).unwrap();
+    animation.render(surface.canvas(), None);
+    let mut bitmap = Bitmap::new();
+    bitmap.alloc_n32_pixels((100, 50), None);
+    assert!(surface.read_pixels_to_bitmap(&bitmap, (0, 0)));
+    assert_eq!(bitmap.get_color((50, 25)), Color::GREEN);
+}
+
+#[test]
+fn test_resource_provider() {
+    use crate::Data;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Provider {
+        requested: RefCell<Vec<(String, String)>>,
+    }
+
+    impl ResourceProvider for Provider {
+        fn load(&self, resource_path: &str, resource_name: &str) -> Option<Data> {
+            self.requested
+                .borrow_mut()
+                .push((resource_path.into(), resource_name.into()));
+            None
+        }
+    }
+
+    let json = r#"{
+        "v": "5.5.2", "fr": 30, "ip": 0, "op": 60, "w": 100, "h": 50,
+        "assets": [{ "id": "image_0", "w": 100, "h": 50, "u": "images/", "p": "img_0.png" }],
+        "layers": [{ "ty": 2, "ind": 1, "refId": "image_0", "ip": 0, "op": 60, "st": 0,
+            "ks": {
+                "o": { "a": 0, "k": 100 },
+                "r": { "a": 0, "k": 0 },
+                "p": { "a": 0, "k": [0, 0, 0] },
+                "a": { "a": 0, "k": [0, 0, 0] },
+                "s": { "a": 0, "k": [100, 100, 100] }
+            }
+        }]
+    }"#;
+
+    let provider = Provider::default();
+    AnimationBuilder::new()
+        .set_resource_provider(&provider)
+        .make(json.as_bytes());
+
+    assert_eq!(
+        *provider.requested.borrow(),
+        vec![("images/".to_string(), "img_0.png".to_string())]
+    );
+}
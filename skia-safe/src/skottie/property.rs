@@ -0,0 +1,180 @@
+use crate::prelude::*;
+use crate::{interop, scalar, Color};
+use skia_bindings::{
+    skottie_ColorPropertyHandle, skottie_OpacityPropertyHandle, skottie_PropertyObserver,
+    skottie_TextPropertyHandle, C_RustPropertyObserver_new, C_skottie_ColorPropertyHandle_delete,
+    C_skottie_ColorPropertyHandle_get, C_skottie_ColorPropertyHandle_set,
+    C_skottie_OpacityPropertyHandle_delete, C_skottie_OpacityPropertyHandle_get,
+    C_skottie_OpacityPropertyHandle_set, C_skottie_TextPropertyHandle_delete,
+    C_skottie_TextPropertyHandle_getText, C_skottie_TextPropertyHandle_getTextSize,
+    C_skottie_TextPropertyHandle_setText, C_skottie_TextPropertyHandle_setTextSize,
+};
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+
+/// Controls a color property of an animation.
+pub struct ColorPropertyHandle(*mut skottie_ColorPropertyHandle);
+
+impl NativeAccess<skottie_ColorPropertyHandle> for ColorPropertyHandle {
+    fn native(&self) -> &skottie_ColorPropertyHandle {
+        unsafe { &*self.0 }
+    }
+
+    fn native_mut(&mut self) -> &mut skottie_ColorPropertyHandle {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl Drop for ColorPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { C_skottie_ColorPropertyHandle_delete(self.0) }
+    }
+}
+
+impl ColorPropertyHandle {
+    pub fn get(&self) -> Color {
+        Color::from_native(unsafe { C_skottie_ColorPropertyHandle_get(self.native()) })
+    }
+
+    pub fn set(&mut self, color: impl Into<Color>) -> &mut Self {
+        unsafe { C_skottie_ColorPropertyHandle_set(self.native_mut(), color.into().into_native()) }
+        self
+    }
+}
+
+/// Controls an opacity property of an animation, the opacity is specified in percent (0..100).
+pub struct OpacityPropertyHandle(*mut skottie_OpacityPropertyHandle);
+
+impl NativeAccess<skottie_OpacityPropertyHandle> for OpacityPropertyHandle {
+    fn native(&self) -> &skottie_OpacityPropertyHandle {
+        unsafe { &*self.0 }
+    }
+
+    fn native_mut(&mut self) -> &mut skottie_OpacityPropertyHandle {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl Drop for OpacityPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { C_skottie_OpacityPropertyHandle_delete(self.0) }
+    }
+}
+
+impl OpacityPropertyHandle {
+    pub fn get(&self) -> scalar {
+        unsafe { C_skottie_OpacityPropertyHandle_get(self.native()) }
+    }
+
+    pub fn set(&mut self, opacity: scalar) -> &mut Self {
+        unsafe { C_skottie_OpacityPropertyHandle_set(self.native_mut(), opacity) }
+        self
+    }
+}
+
+/// Controls a text property of an animation.
+pub struct TextPropertyHandle(*mut skottie_TextPropertyHandle);
+
+impl NativeAccess<skottie_TextPropertyHandle> for TextPropertyHandle {
+    fn native(&self) -> &skottie_TextPropertyHandle {
+        unsafe { &*self.0 }
+    }
+
+    fn native_mut(&mut self) -> &mut skottie_TextPropertyHandle {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl Drop for TextPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { C_skottie_TextPropertyHandle_delete(self.0) }
+    }
+}
+
+impl TextPropertyHandle {
+    pub fn text(&self) -> String {
+        let mut text = interop::String::default();
+        unsafe { C_skottie_TextPropertyHandle_getText(self.native(), text.native_mut()) }
+        text.as_str().into()
+    }
+
+    pub fn set_text(&mut self, text: impl AsRef<str>) -> &mut Self {
+        let text = text.as_ref();
+        unsafe {
+            C_skottie_TextPropertyHandle_setText(self.native_mut(), text.as_ptr() as _, text.len())
+        }
+        self
+    }
+
+    pub fn text_size(&self) -> scalar {
+        unsafe { C_skottie_TextPropertyHandle_getTextSize(self.native()) }
+    }
+
+    pub fn set_text_size(&mut self, text_size: scalar) -> &mut Self {
+        unsafe { C_skottie_TextPropertyHandle_setTextSize(self.native_mut(), text_size) }
+        self
+    }
+}
+
+/// Receives the handles of the animation's properties while the animation is built.
+///
+/// The handles stay valid after the animation is built, so they can be kept and used to
+/// change the properties of the animation later on.
+pub trait PropertyObserver {
+    fn on_color_property(&mut self, _node_name: &str, _handle: ColorPropertyHandle) {}
+
+    fn on_opacity_property(&mut self, _node_name: &str, _handle: OpacityPropertyHandle) {}
+
+    fn on_text_property(&mut self, _node_name: &str, _handle: TextPropertyHandle) {}
+}
+
+/// Creates a native property observer that forwards to `observer`.
+/// The returned pointer is valid only as long as the `observer` reference is.
+pub(crate) fn new_native(
+    observer: &mut &mut dyn PropertyObserver,
+) -> *mut skottie_PropertyObserver {
+    unsafe {
+        C_RustPropertyObserver_new(
+            observer as *mut &mut dyn PropertyObserver as *mut c_void,
+            Some(on_color),
+            Some(on_opacity),
+            Some(on_text),
+        )
+    }
+}
+
+unsafe extern "C" fn on_color(
+    observer: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut skottie_ColorPropertyHandle,
+) {
+    catch_panic_or((), || {
+        let observer = &mut *(observer as *mut &mut dyn PropertyObserver);
+        let node_name = CStr::from_ptr(node_name).to_string_lossy();
+        observer.on_color_property(&node_name, ColorPropertyHandle(handle))
+    })
+}
+
+unsafe extern "C" fn on_opacity(
+    observer: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut skottie_OpacityPropertyHandle,
+) {
+    catch_panic_or((), || {
+        let observer = &mut *(observer as *mut &mut dyn PropertyObserver);
+        let node_name = CStr::from_ptr(node_name).to_string_lossy();
+        observer.on_opacity_property(&node_name, OpacityPropertyHandle(handle))
+    })
+}
+
+unsafe extern "C" fn on_text(
+    observer: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut skottie_TextPropertyHandle,
+) {
+    catch_panic_or((), || {
+        let observer = &mut *(observer as *mut &mut dyn PropertyObserver);
+        let node_name = CStr::from_ptr(node_name).to_string_lossy();
+        observer.on_text_property(&node_name, TextPropertyHandle(handle))
+    })
+}
@@ -0,0 +1,56 @@
+use crate::prelude::*;
+use crate::Data;
+use skia_bindings::{skottie_ResourceProvider, C_RustResourceProvider_new, SkData};
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::ptr;
+
+/// Loads the external resources an animation refers to, like images and fonts.
+pub trait ResourceProvider {
+    /// Loads a generic resource, for example an encoded image, specified by its path and name.
+    fn load(&self, resource_path: &str, resource_name: &str) -> Option<Data>;
+
+    /// Loads the data of a font that is referenced by its name and url.
+    fn load_font(&self, _name: &str, _url: &str) -> Option<Data> {
+        None
+    }
+}
+
+/// Creates a native resource provider that forwards to `provider`.
+/// The returned pointer is valid only as long as the `provider` reference is.
+pub(crate) fn new_native(provider: &&dyn ResourceProvider) -> *mut skottie_ResourceProvider {
+    unsafe {
+        C_RustResourceProvider_new(
+            provider as *const &dyn ResourceProvider as *mut c_void,
+            Some(load),
+            Some(load_font),
+        )
+    }
+}
+
+unsafe extern "C" fn load(
+    provider: *mut c_void,
+    resource_path: *const c_char,
+    resource_name: *const c_char,
+) -> *mut SkData {
+    // a panic is treated like a resource that can not be loaded.
+    catch_panic_or(ptr::null_mut(), || {
+        let provider = &*(provider as *const &dyn ResourceProvider);
+        let resource_path = CStr::from_ptr(resource_path).to_string_lossy();
+        let resource_name = CStr::from_ptr(resource_name).to_string_lossy();
+        provider.load(&resource_path, &resource_name).shared_ptr() as _
+    })
+}
+
+unsafe extern "C" fn load_font(
+    provider: *mut c_void,
+    name: *const c_char,
+    url: *const c_char,
+) -> *mut SkData {
+    catch_panic_or(ptr::null_mut(), || {
+        let provider = &*(provider as *const &dyn ResourceProvider);
+        let name = CStr::from_ptr(name).to_string_lossy();
+        let url = CStr::from_ptr(url).to_string_lossy();
+        provider.load_font(&name, &url).shared_ptr() as _
+    })
+}
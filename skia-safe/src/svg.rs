@@ -1,2 +1,5 @@
 mod canvas;
 pub use self::canvas::*;
+
+mod dom;
+pub use self::dom::*;
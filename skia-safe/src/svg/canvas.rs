@@ -1,17 +1,16 @@
-use crate::interop::DynamicMemoryWStream;
+use crate::document::sink;
 use crate::prelude::*;
 use crate::{Data, Rect};
 use skia_bindings::{C_SkCanvas_delete, C_SkSVGCanvas_Make, SkCanvas};
 use std::ops::{Deref, DerefMut};
-use std::pin::Pin;
-use std::ptr;
+use std::{io, ptr};
 
-pub struct Canvas {
+pub struct Canvas<Sink = sink::Memory> {
     canvas: *mut SkCanvas,
-    stream: Pin<Box<DynamicMemoryWStream>>,
+    sink: Sink,
 }
 
-impl Drop for Canvas {
+impl<Sink> Drop for Canvas<Sink> {
     fn drop(&mut self) {
         unsafe {
             C_SkCanvas_delete(self.canvas);
@@ -19,7 +18,7 @@ impl Drop for Canvas {
     }
 }
 
-impl Deref for Canvas {
+impl<Sink> Deref for Canvas<Sink> {
     type Target = crate::Canvas;
 
     fn deref(&self) -> &Self::Target {
@@ -27,7 +26,7 @@ impl Deref for Canvas {
     }
 }
 
-impl DerefMut for Canvas {
+impl<Sink> DerefMut for Canvas<Sink> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         crate::Canvas::borrow_from_native(unsafe { &mut *self.canvas })
     }
@@ -37,9 +36,9 @@ impl Canvas {
     /// Creates a new SVG canvas.
     pub fn new(bounds: impl AsRef<Rect>) -> Canvas {
         let bounds = bounds.as_ref();
-        let mut stream = Box::pin(DynamicMemoryWStream::new());
-        let canvas = unsafe { C_SkSVGCanvas_Make(bounds.native(), &mut stream.native_mut()._base) };
-        Canvas { canvas, stream }
+        let mut sink = sink::Memory::new();
+        let canvas = unsafe { C_SkSVGCanvas_Make(bounds.native(), sink.stream_mut()) };
+        Canvas { canvas, sink }
     }
 
     /// Ends the Canvas drawing and returns the resulting SVG.
@@ -52,7 +51,26 @@ impl Canvas {
             C_SkCanvas_delete(self.canvas);
         }
         self.canvas = ptr::null_mut();
-        self.stream.detach_as_data()
+        self.sink.detach_as_data()
+    }
+}
+
+impl<'a> Canvas<sink::Writer<'a>> {
+    /// Creates a new SVG canvas that writes the SVG into writer.
+    pub fn new_with_writer(bounds: impl AsRef<Rect>, writer: impl io::Write + 'a) -> Self {
+        let bounds = bounds.as_ref();
+        let mut sink = sink::Writer::new(writer);
+        let canvas = unsafe { C_SkSVGCanvas_Make(bounds.native(), sink.stream_mut()) };
+        Canvas { canvas, sink }
+    }
+
+    /// Ends the Canvas drawing, writes the remaining SVG, and flushes the writer.
+    pub fn end(mut self) -> io::Result<()> {
+        unsafe {
+            C_SkCanvas_delete(self.canvas);
+        }
+        self.canvas = ptr::null_mut();
+        self.sink.flush()
     }
 }
 
@@ -78,3 +96,14 @@ fn test_svg_without_ending() {
     let paint = Paint::default();
     canvas.draw_circle((10, 10), 10.0, &paint);
 }
+
+#[test]
+fn test_svg_with_writer() {
+    use crate::Paint;
+
+    let mut svg = Vec::new();
+    let mut canvas = Canvas::new_with_writer(&Rect::from_size((20, 20)), &mut svg);
+    canvas.draw_circle((10, 10), 10.0, &Paint::default());
+    canvas.end().unwrap();
+    assert!(String::from_utf8_lossy(&svg).contains(r#"</svg>"#));
+}
@@ -0,0 +1,108 @@
+use crate::interop::RustStream;
+use crate::prelude::*;
+use crate::{Canvas, Size};
+use skia_bindings::{
+    C_SkSVGDOM_MakeFromStream, C_SkSVGDOM_containerSize, C_SkSVGDOM_render,
+    C_SkSVGDOM_setContainerSize, SkRefCntBase, SkSVGDOM,
+};
+use std::io;
+
+impl NativeRefCountedBase for SkSVGDOM {
+    type Base = SkRefCntBase;
+
+    fn ref_counted_base(&self) -> &Self::Base {
+        &self._base._base
+    }
+}
+
+/// A parsed SVG document that can be rendered onto a Canvas.
+pub struct Dom {
+    native: RCHandle<SkSVGDOM>,
+    intrinsic_size: Size,
+}
+
+impl NativeAccess<SkSVGDOM> for Dom {
+    fn native(&self) -> &SkSVGDOM {
+        self.native.native()
+    }
+
+    fn native_mut(&mut self) -> &mut SkSVGDOM {
+        self.native.native_mut()
+    }
+}
+
+impl Dom {
+    pub fn from_bytes(bytes: &[u8]) -> Option<Dom> {
+        Self::from_stream(bytes)
+    }
+
+    pub fn from_stream(stream: impl io::Read) -> Option<Dom> {
+        let mut stream = RustStream::new(stream);
+        RCHandle::from_ptr(unsafe { C_SkSVGDOM_MakeFromStream(stream.stream_mut()) }).map(
+            |native: RCHandle<SkSVGDOM>| {
+                // after loading, the container size is initialized to the intrinsic size of the
+                // document.
+                let intrinsic_size = Self::native_container_size(&native);
+                Dom {
+                    native,
+                    intrinsic_size,
+                }
+            },
+        )
+    }
+
+    /// The size specified by the document's root element, which is empty if the document
+    /// does not specify one.
+    pub fn intrinsic_size(&self) -> Size {
+        self.intrinsic_size
+    }
+
+    pub fn container_size(&self) -> Size {
+        Self::native_container_size(&self.native)
+    }
+
+    /// Sets the size of the viewport relative lengths and percentages are resolved against.
+    pub fn set_container_size(&mut self, size: impl Into<Size>) -> &mut Self {
+        let size = size.into();
+        unsafe { C_SkSVGDOM_setContainerSize(self.native_mut(), size.native()) }
+        self
+    }
+
+    pub fn render(&self, mut canvas: impl AsMut<Canvas>) {
+        unsafe { C_SkSVGDOM_render(self.native(), canvas.as_mut().native_mut()) }
+    }
+
+    fn native_container_size(native: &RCHandle<SkSVGDOM>) -> Size {
+        let mut size = Size::default();
+        unsafe { C_SkSVGDOM_containerSize(native.native(), size.native_mut()) }
+        size
+    }
+}
+
+#[cfg(test)]
+const TEST_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+    <rect x="0" y="0" width="20" height="10" fill="blue"/>
+</svg>"#;
+
+#[test]
+fn test_dom_sizes() {
+    let mut dom = Dom::from_bytes(TEST_SVG.as_bytes()).unwrap();
+    assert_eq!(dom.intrinsic_size(), Size::new(20.0, 10.0));
+    assert_eq!(dom.container_size(), Size::new(20.0, 10.0));
+    dom.set_container_size((40, 20));
+    assert_eq!(dom.container_size(), Size::new(40.0, 20.0));
+    assert_eq!(dom.intrinsic_size(), Size::new(20.0, 10.0));
+}
+
+#[test]
+fn test_dom_render() {
+    use crate::{Bitmap, Color, Surface};
+
+    let dom = Dom::from_bytes(TEST_SVG.as_bytes()).unwrap();
+    let mut surface = Surface::new_raster_n32_premul((20, 10)).unwrap();
+    dom.render(surface.canvas());
+    let mut bitmap = Bitmap::new();
+    bitmap.alloc_n32_pixels((20, 10), None);
+    assert!(surface.read_pixels_to_bitmap(&bitmap, (0, 0)));
+    assert_eq!(bitmap.get_color((10, 5)), Color::BLUE);
+}
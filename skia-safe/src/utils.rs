@@ -4,6 +4,9 @@ pub use _3d::*;
 mod camera;
 pub use camera::*;
 
+mod canvas_backend;
+pub use canvas_backend::*;
+
 pub mod interpolator;
 pub use interpolator::Interpolator;
 
@@ -0,0 +1,995 @@
+use crate::canvas::lattice::RectType;
+use crate::canvas::{Lattice, PointMode, SrcRectConstraint};
+use crate::prelude::*;
+use crate::utils::shadow_utils::ShadowFlags;
+use crate::{
+    scalar, BlendMode, Canvas, ClipOp, Color, Data, IRect, ISize, Image, Matrix, OwnedCanvas,
+    Paint, Path, Point, Point3, RRect, RSXform, Rect, Region, TextBlob, Vertices,
+};
+use skia_bindings::{
+    C_RustCanvas_new, C_SkCanvas_ImageSetEntry_get, RustCanvasBackend, SkBlendMode,
+    SkCanvas_ImageSetEntry, SkCanvas_Lattice, SkCanvas_PointMode,
+    SkCanvas_QuadAAFlags_kBottom_QuadAAFlag, SkCanvas_QuadAAFlags_kLeft_QuadAAFlag,
+    SkCanvas_QuadAAFlags_kRight_QuadAAFlag, SkCanvas_QuadAAFlags_kTop_QuadAAFlag,
+    SkCanvas_SrcRectConstraint, SkClipOp, SkColor, SkData, SkIRect, SkImage, SkMatrix, SkPaint,
+    SkPath, SkPoint, SkPoint3, SkRRect, SkRSXform, SkRect, SkRegion, SkTextBlob, SkVertices,
+};
+use std::any::Any;
+use std::ffi::{c_void, CStr};
+use std::os::raw::{c_char, c_int, c_uint};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, thread};
+
+bitflags! {
+    /// The edges of a quad that are anti-aliased.
+    pub struct QuadAAFlags: u32 {
+        const LEFT = SkCanvas_QuadAAFlags_kLeft_QuadAAFlag as _;
+        const TOP = SkCanvas_QuadAAFlags_kTop_QuadAAFlag as _;
+        const RIGHT = SkCanvas_QuadAAFlags_kRight_QuadAAFlag as _;
+        const BOTTOM = SkCanvas_QuadAAFlags_kBottom_QuadAAFlag as _;
+        const ALL = Self::LEFT.bits | Self::TOP.bits | Self::RIGHT.bits | Self::BOTTOM.bits;
+    }
+}
+
+/// The shadow of a path, as it is drawn by `Canvas::draw_shadow()`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ShadowRec {
+    pub z_plane_params: Point3,
+    pub light_pos: Point3,
+    pub light_radius: scalar,
+    pub ambient_color: Color,
+    pub spot_color: Color,
+    pub flags: ShadowFlags,
+}
+
+/// An image of an edge anti-aliased image set.
+#[derive(Clone)]
+pub struct ImageSetEntry {
+    pub image: Image,
+    pub src_rect: Rect,
+    pub dst_rect: Rect,
+    /// The index into the pre-view matrices of the set, if the image has a matrix.
+    pub matrix_index: Option<usize>,
+    pub alpha: f32,
+    pub aa_flags: QuadAAFlags,
+    /// If `true`, the next 4 points of the set's destination clips are the clip of this image.
+    pub has_clip: bool,
+}
+
+/// The clip op of a clip that is forwarded to a `CanvasBackend`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum BackendClipOp {
+    Difference,
+    Intersect,
+    /// One of the deprecated expanding clip ops of Skia, given by its underlying value.
+    Expanding(i32),
+}
+
+impl From<ClipOp> for BackendClipOp {
+    fn from(op: ClipOp) -> Self {
+        match op {
+            ClipOp::Difference => BackendClipOp::Difference,
+            ClipOp::Intersect => BackendClipOp::Intersect,
+        }
+    }
+}
+
+impl BackendClipOp {
+    /// Returns the `ClipOp`, or `None` if this is a deprecated expanding clip op.
+    pub fn clip_op(self) -> Option<ClipOp> {
+        match self {
+            BackendClipOp::Difference => Some(ClipOp::Difference),
+            BackendClipOp::Intersect => Some(ClipOp::Intersect),
+            BackendClipOp::Expanding(_) => None,
+        }
+    }
+
+    fn from_native(op: c_int) -> Self {
+        if op == SkClipOp::kDifference as c_int {
+            BackendClipOp::Difference
+        } else if op == SkClipOp::kIntersect as c_int {
+            BackendClipOp::Intersect
+        } else {
+            BackendClipOp::Expanding(op)
+        }
+    }
+}
+
+/// Receives the calls of a canvas created with `Canvas::from_backend()`.
+///
+/// Coordinates are passed as they are given to the canvas, the current transformation is
+/// reported with `did_concat()` and `did_set_matrix()` and saved and restored along with the
+/// clip by `will_save()`, `will_save_layer()` and `did_restore()`.
+///
+/// Bitmaps are forwarded as images, and pictures and drawables are played back into the canvas.
+/// Clips with the deprecated expanding clip ops of Skia are forwarded as
+/// `BackendClipOp::Expanding`.
+///
+/// A panic of the backend is not unwound into Skia. The canvas stops forwarding calls after the
+/// first panic and resumes it when it is dropped.
+pub trait CanvasBackend {
+    fn will_save(&mut self) {}
+
+    fn will_save_layer(&mut self, _bounds: Option<&Rect>, _paint: Option<&Paint>) {}
+
+    fn did_restore(&mut self) {}
+
+    fn did_concat(&mut self, _matrix: &Matrix) {}
+
+    fn did_set_matrix(&mut self, _matrix: &Matrix) {}
+
+    fn on_clip_rect(&mut self, _rect: &Rect, _op: BackendClipOp, _anti_alias: bool) {}
+
+    fn on_clip_rrect(&mut self, _rrect: &RRect, _op: BackendClipOp, _anti_alias: bool) {}
+
+    fn on_clip_path(&mut self, _path: &Path, _op: BackendClipOp, _anti_alias: bool) {}
+
+    /// The region is specified in device coordinates.
+    fn on_clip_region(&mut self, _device_region: &Region, _op: BackendClipOp) {}
+
+    fn on_draw_paint(&mut self, _paint: &Paint) {}
+
+    fn on_draw_points(&mut self, _mode: PointMode, _points: &[Point], _paint: &Paint) {}
+
+    fn on_draw_rect(&mut self, _rect: &Rect, _paint: &Paint) {}
+
+    fn on_draw_region(&mut self, _region: &Region, _paint: &Paint) {}
+
+    fn on_draw_oval(&mut self, _oval: &Rect, _paint: &Paint) {}
+
+    fn on_draw_arc(
+        &mut self,
+        _oval: &Rect,
+        _start_angle: scalar,
+        _sweep_angle: scalar,
+        _use_center: bool,
+        _paint: &Paint,
+    ) {
+    }
+
+    fn on_draw_rrect(&mut self, _rrect: &RRect, _paint: &Paint) {}
+
+    fn on_draw_drrect(&mut self, _outer: &RRect, _inner: &RRect, _paint: &Paint) {}
+
+    fn on_draw_path(&mut self, _path: &Path, _paint: &Paint) {}
+
+    fn on_draw_text_blob(&mut self, _blob: &TextBlob, _origin: Point, _paint: &Paint) {}
+
+    fn on_draw_image(&mut self, _image: &Image, _left_top: Point, _paint: Option<&Paint>) {}
+
+    fn on_draw_image_rect(
+        &mut self,
+        _image: &Image,
+        _src: Option<&Rect>,
+        _dst: &Rect,
+        _paint: Option<&Paint>,
+        _constraint: SrcRectConstraint,
+    ) {
+    }
+
+    fn on_draw_vertices(&mut self, _vertices: &Vertices, _mode: BlendMode, _paint: &Paint) {}
+
+    fn on_draw_annotation(&mut self, _rect: &Rect, _key: &str, _value: Option<&Data>) {}
+
+    fn on_draw_patch(
+        &mut self,
+        _cubics: &[Point; 12],
+        _colors: Option<&[Color; 4]>,
+        _tex_coords: Option<&[Point; 4]>,
+        _mode: BlendMode,
+        _paint: &Paint,
+    ) {
+    }
+
+    fn on_draw_image_nine(
+        &mut self,
+        _image: &Image,
+        _center: &IRect,
+        _dst: &Rect,
+        _paint: Option<&Paint>,
+    ) {
+    }
+
+    fn on_draw_image_lattice(
+        &mut self,
+        _image: &Image,
+        _lattice: &Lattice,
+        _dst: &Rect,
+        _paint: Option<&Paint>,
+    ) {
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_draw_atlas(
+        &mut self,
+        _atlas: &Image,
+        _xforms: &[RSXform],
+        _tex: &[Rect],
+        _colors: Option<&[Color]>,
+        _mode: BlendMode,
+        _cull_rect: Option<&Rect>,
+        _paint: Option<&Paint>,
+    ) {
+    }
+
+    fn on_draw_shadow_rec(&mut self, _path: &Path, _rec: &ShadowRec) {}
+
+    fn on_draw_edge_aa_quad(
+        &mut self,
+        _rect: &Rect,
+        _clip: Option<&[Point; 4]>,
+        _aa_flags: QuadAAFlags,
+        _color: Color,
+        _mode: BlendMode,
+    ) {
+    }
+
+    fn on_draw_edge_aa_image_set(
+        &mut self,
+        _image_set: &[ImageSetEntry],
+        _dst_clips: &[Point],
+        _pre_view_matrices: &[Matrix],
+        _paint: Option<&Paint>,
+        _constraint: SrcRectConstraint,
+    ) {
+    }
+}
+
+impl Canvas {
+    /// Creates a canvas of the given size that does not draw, but forwards its calls to
+    /// `backend`.
+    pub fn from_backend<'a>(
+        size: impl Into<ISize>,
+        backend: &'a mut dyn CanvasBackend,
+    ) -> OwnedCanvas<'a> {
+        let size = size.into();
+        // a thin pointer to pass through the C++ canvas, owned by the OwnedCanvas.
+        let context = Box::into_raw(Box::new(BackendContext {
+            backend,
+            panic: None,
+        }));
+        let backend = RustCanvasBackend {
+            backend: context as *mut c_void,
+            willSave: Some(will_save),
+            willSaveLayer: Some(will_save_layer),
+            didRestore: Some(did_restore),
+            didConcat: Some(did_concat),
+            didSetMatrix: Some(did_set_matrix),
+            clipRect: Some(clip_rect),
+            clipRRect: Some(clip_rrect),
+            clipPath: Some(clip_path),
+            clipRegion: Some(clip_region),
+            drawPaint: Some(draw_paint),
+            drawPoints: Some(draw_points),
+            drawRect: Some(draw_rect),
+            drawRegion: Some(draw_region),
+            drawOval: Some(draw_oval),
+            drawArc: Some(draw_arc),
+            drawRRect: Some(draw_rrect),
+            drawDRRect: Some(draw_drrect),
+            drawPath: Some(draw_path),
+            drawTextBlob: Some(draw_text_blob),
+            drawImage: Some(draw_image),
+            drawImageRect: Some(draw_image_rect),
+            drawVertices: Some(draw_vertices),
+            drawAnnotation: Some(draw_annotation),
+            drawPatch: Some(draw_patch),
+            drawImageNine: Some(draw_image_nine),
+            drawImageLattice: Some(draw_image_lattice),
+            drawAtlas: Some(draw_atlas),
+            drawShadowRec: Some(draw_shadow_rec),
+            drawEdgeAAQuad: Some(draw_edge_aa_quad),
+            drawEdgeAAImageSet: Some(draw_edge_aa_image_set),
+        };
+        Canvas::own_from_native_ptr(unsafe { C_RustCanvas_new(size.width, size.height, &backend) })
+            .unwrap()
+            .on_deleted(move || {
+                let context = unsafe { Box::from_raw(context) };
+                if let Some(panic) = context.panic {
+                    // don't abort if the canvas is dropped while unwinding.
+                    if !thread::panicking() {
+                        panic::resume_unwind(panic)
+                    }
+                }
+            })
+    }
+}
+
+struct BackendContext<'a> {
+    backend: &'a mut dyn CanvasBackend,
+    // panics must not unwind into Skia, so they are resumed after the canvas is deleted.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+// Calls `f` with the backend, unless a call has panicked before.
+unsafe fn forward(context: *mut c_void, f: impl FnOnce(&mut dyn CanvasBackend)) {
+    let context = &mut *(context as *mut BackendContext);
+    if context.panic.is_none() {
+        let backend = &mut *context.backend;
+        context.panic = panic::catch_unwind(AssertUnwindSafe(|| f(backend))).err();
+    }
+}
+
+// Skia passes null pointers for empty arrays.
+unsafe fn slice_or_empty<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+unsafe extern "C" fn will_save(b: *mut c_void) {
+    forward(b, |backend| backend.will_save())
+}
+
+unsafe extern "C" fn will_save_layer(b: *mut c_void, bounds: *const SkRect, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.will_save_layer(
+            bounds.as_ref().map(Rect::from_native_ref),
+            paint.as_ref().map(Paint::from_native_ref),
+        )
+    })
+}
+
+unsafe extern "C" fn did_restore(b: *mut c_void) {
+    forward(b, |backend| backend.did_restore())
+}
+
+unsafe extern "C" fn did_concat(b: *mut c_void, matrix: *const SkMatrix) {
+    forward(b, |backend| {
+        backend.did_concat(Matrix::from_native_ref(&*matrix))
+    })
+}
+
+unsafe extern "C" fn did_set_matrix(b: *mut c_void, matrix: *const SkMatrix) {
+    forward(b, |backend| {
+        backend.did_set_matrix(Matrix::from_native_ref(&*matrix))
+    })
+}
+
+unsafe extern "C" fn clip_rect(b: *mut c_void, rect: *const SkRect, op: c_int, aa: bool) {
+    let op = BackendClipOp::from_native(op);
+    forward(b, |backend| {
+        backend.on_clip_rect(Rect::from_native_ref(&*rect), op, aa)
+    })
+}
+
+unsafe extern "C" fn clip_rrect(b: *mut c_void, rrect: *const SkRRect, op: c_int, aa: bool) {
+    let op = BackendClipOp::from_native(op);
+    forward(b, |backend| {
+        backend.on_clip_rrect(RRect::from_native_ref(&*rrect), op, aa)
+    })
+}
+
+unsafe extern "C" fn clip_path(b: *mut c_void, path: *const SkPath, op: c_int, aa: bool) {
+    let op = BackendClipOp::from_native(op);
+    forward(b, |backend| {
+        backend.on_clip_path(Path::from_native_ref(&*path), op, aa)
+    })
+}
+
+unsafe extern "C" fn clip_region(b: *mut c_void, region: *const SkRegion, op: c_int) {
+    let op = BackendClipOp::from_native(op);
+    forward(b, |backend| {
+        backend.on_clip_region(Region::from_native_ref(&*region), op)
+    })
+}
+
+unsafe extern "C" fn draw_paint(b: *mut c_void, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.on_draw_paint(Paint::from_native_ref(&*paint))
+    })
+}
+
+unsafe extern "C" fn draw_points(
+    b: *mut c_void,
+    mode: SkCanvas_PointMode,
+    count: usize,
+    points: *const SkPoint,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        backend.on_draw_points(
+            PointMode::from_native(mode),
+            slice_or_empty(points as *const Point, count),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_rect(b: *mut c_void, rect: *const SkRect, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.on_draw_rect(
+            Rect::from_native_ref(&*rect),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_region(b: *mut c_void, region: *const SkRegion, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.on_draw_region(
+            Region::from_native_ref(&*region),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_oval(b: *mut c_void, oval: *const SkRect, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.on_draw_oval(
+            Rect::from_native_ref(&*oval),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_arc(
+    b: *mut c_void,
+    oval: *const SkRect,
+    start_angle: scalar,
+    sweep_angle: scalar,
+    use_center: bool,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        backend.on_draw_arc(
+            Rect::from_native_ref(&*oval),
+            start_angle,
+            sweep_angle,
+            use_center,
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_rrect(b: *mut c_void, rrect: *const SkRRect, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.on_draw_rrect(
+            RRect::from_native_ref(&*rrect),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_drrect(
+    b: *mut c_void,
+    outer: *const SkRRect,
+    inner: *const SkRRect,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        backend.on_draw_drrect(
+            RRect::from_native_ref(&*outer),
+            RRect::from_native_ref(&*inner),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_path(b: *mut c_void, path: *const SkPath, paint: *const SkPaint) {
+    forward(b, |backend| {
+        backend.on_draw_path(
+            Path::from_native_ref(&*path),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_text_blob(
+    b: *mut c_void,
+    blob: *const SkTextBlob,
+    x: scalar,
+    y: scalar,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        let blob = TextBlob::from_unshared_ptr(blob as *mut _).unwrap();
+        backend.on_draw_text_blob(&blob, Point::new(x, y), Paint::from_native_ref(&*paint))
+    })
+}
+
+unsafe extern "C" fn draw_image(
+    b: *mut c_void,
+    image: *const SkImage,
+    left: scalar,
+    top: scalar,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        let image = Image::from_unshared_ptr(image as *mut _).unwrap();
+        backend.on_draw_image(
+            &image,
+            Point::new(left, top),
+            paint.as_ref().map(Paint::from_native_ref),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_image_rect(
+    b: *mut c_void,
+    image: *const SkImage,
+    src: *const SkRect,
+    dst: *const SkRect,
+    paint: *const SkPaint,
+    constraint: SkCanvas_SrcRectConstraint,
+) {
+    forward(b, |backend| {
+        let image = Image::from_unshared_ptr(image as *mut _).unwrap();
+        backend.on_draw_image_rect(
+            &image,
+            src.as_ref().map(Rect::from_native_ref),
+            Rect::from_native_ref(&*dst),
+            paint.as_ref().map(Paint::from_native_ref),
+            SrcRectConstraint::from_native(constraint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_vertices(
+    b: *mut c_void,
+    vertices: *const SkVertices,
+    mode: SkBlendMode,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        let vertices = Vertices::from_unshared_ptr(vertices as *mut _).unwrap();
+        backend.on_draw_vertices(
+            &vertices,
+            BlendMode::from_native(mode),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_annotation(
+    b: *mut c_void,
+    rect: *const SkRect,
+    key: *const c_char,
+    value: *const SkData,
+) {
+    forward(b, |backend| {
+        let key = CStr::from_ptr(key).to_string_lossy();
+        let value = Data::from_unshared_ptr(value as *mut _);
+        backend.on_draw_annotation(Rect::from_native_ref(&*rect), &key, value.as_ref())
+    })
+}
+
+unsafe extern "C" fn draw_patch(
+    b: *mut c_void,
+    cubics: *const SkPoint,
+    colors: *const SkColor,
+    tex_coords: *const SkPoint,
+    mode: SkBlendMode,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        backend.on_draw_patch(
+            &*(cubics as *const [Point; 12]),
+            (colors as *const [Color; 4]).as_ref(),
+            (tex_coords as *const [Point; 4]).as_ref(),
+            BlendMode::from_native(mode),
+            Paint::from_native_ref(&*paint),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_image_nine(
+    b: *mut c_void,
+    image: *const SkImage,
+    center: *const SkIRect,
+    dst: *const SkRect,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        let image = Image::from_unshared_ptr(image as *mut _).unwrap();
+        backend.on_draw_image_nine(
+            &image,
+            IRect::from_native_ref(&*center),
+            Rect::from_native_ref(&*dst),
+            paint.as_ref().map(Paint::from_native_ref),
+        )
+    })
+}
+
+unsafe extern "C" fn draw_image_lattice(
+    b: *mut c_void,
+    image: *const SkImage,
+    lattice: *const SkCanvas_Lattice,
+    dst: *const SkRect,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        let image = Image::from_unshared_ptr(image as *mut _).unwrap();
+        let lattice = &*lattice;
+        let x_count = lattice.fXCount.max(0) as usize;
+        let y_count = lattice.fYCount.max(0) as usize;
+        let rect_count = (x_count + 1) * (y_count + 1);
+        let rect_types = lattice.fRectTypes as *const RectType;
+        let colors = lattice.fColors as *const Color;
+        let lattice = Lattice {
+            x_divs: slice_or_empty(lattice.fXDivs, x_count),
+            y_divs: slice_or_empty(lattice.fYDivs, y_count),
+            rect_types: rect_types
+                .as_ref()
+                .map(|_| slice::from_raw_parts(rect_types, rect_count)),
+            bounds: lattice
+                .fBounds
+                .as_ref()
+                .map(|bounds| *IRect::from_native_ref(bounds)),
+            colors: colors
+                .as_ref()
+                .map(|_| slice::from_raw_parts(colors, rect_count)),
+        };
+        backend.on_draw_image_lattice(
+            &image,
+            &lattice,
+            Rect::from_native_ref(&*dst),
+            paint.as_ref().map(Paint::from_native_ref),
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn draw_atlas(
+    b: *mut c_void,
+    atlas: *const SkImage,
+    xforms: *const SkRSXform,
+    tex: *const SkRect,
+    colors: *const SkColor,
+    count: c_int,
+    mode: SkBlendMode,
+    cull_rect: *const SkRect,
+    paint: *const SkPaint,
+) {
+    forward(b, |backend| {
+        let atlas = Image::from_unshared_ptr(atlas as *mut _).unwrap();
+        let count = count.max(0) as usize;
+        let colors = colors as *const Color;
+        backend.on_draw_atlas(
+            &atlas,
+            slice_or_empty(xforms as *const RSXform, count),
+            slice_or_empty(tex as *const Rect, count),
+            colors.as_ref().map(|_| slice_or_empty(colors, count)),
+            BlendMode::from_native(mode),
+            cull_rect.as_ref().map(Rect::from_native_ref),
+            paint.as_ref().map(Paint::from_native_ref),
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn draw_shadow_rec(
+    b: *mut c_void,
+    path: *const SkPath,
+    z_plane_params: *const SkPoint3,
+    light_pos: *const SkPoint3,
+    light_radius: scalar,
+    ambient_color: SkColor,
+    spot_color: SkColor,
+    flags: u32,
+) {
+    forward(b, |backend| {
+        let rec = ShadowRec {
+            z_plane_params: Point3::from_native(*z_plane_params),
+            light_pos: Point3::from_native(*light_pos),
+            light_radius,
+            ambient_color: Color::from_native(ambient_color),
+            spot_color: Color::from_native(spot_color),
+            flags: ShadowFlags::from_bits_truncate(flags),
+        };
+        backend.on_draw_shadow_rec(Path::from_native_ref(&*path), &rec)
+    })
+}
+
+unsafe extern "C" fn draw_edge_aa_quad(
+    b: *mut c_void,
+    rect: *const SkRect,
+    clip: *const SkPoint,
+    aa_flags: c_uint,
+    color: SkColor,
+    mode: SkBlendMode,
+) {
+    forward(b, |backend| {
+        backend.on_draw_edge_aa_quad(
+            Rect::from_native_ref(&*rect),
+            (clip as *const [Point; 4]).as_ref(),
+            QuadAAFlags::from_bits_truncate(aa_flags),
+            Color::from_native(color),
+            BlendMode::from_native(mode),
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn draw_edge_aa_image_set(
+    b: *mut c_void,
+    image_set: *const SkCanvas_ImageSetEntry,
+    count: c_int,
+    dst_clips: *const SkPoint,
+    dst_clip_count: c_int,
+    pre_view_matrices: *const SkMatrix,
+    pre_view_matrix_count: c_int,
+    paint: *const SkPaint,
+    constraint: SkCanvas_SrcRectConstraint,
+) {
+    forward(b, |backend| {
+        let image_set: Vec<ImageSetEntry> = (0..count.max(0))
+            .map(|index| {
+                let mut image = ptr::null();
+                let mut src_rect = Rect::default();
+                let mut dst_rect = Rect::default();
+                let mut matrix_index = -1;
+                let mut alpha = 1.0;
+                let mut aa_flags = 0;
+                let mut has_clip = false;
+                C_SkCanvas_ImageSetEntry_get(
+                    image_set,
+                    index,
+                    &mut image,
+                    src_rect.native_mut(),
+                    dst_rect.native_mut(),
+                    &mut matrix_index,
+                    &mut alpha,
+                    &mut aa_flags,
+                    &mut has_clip,
+                );
+                ImageSetEntry {
+                    image: Image::from_unshared_ptr(image as *mut _).unwrap(),
+                    src_rect,
+                    dst_rect,
+                    matrix_index: if matrix_index >= 0 {
+                        Some(matrix_index as usize)
+                    } else {
+                        None
+                    },
+                    alpha,
+                    aa_flags: QuadAAFlags::from_bits_truncate(aa_flags),
+                    has_clip,
+                }
+            })
+            .collect();
+        backend.on_draw_edge_aa_image_set(
+            &image_set,
+            slice_or_empty(dst_clips as *const Point, dst_clip_count.max(0) as usize),
+            slice_or_empty(
+                pre_view_matrices as *const Matrix,
+                pre_view_matrix_count.max(0) as usize,
+            ),
+            paint.as_ref().map(Paint::from_native_ref),
+            SrcRectConstraint::from_native(constraint),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Font, PictureRecorder};
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl CanvasBackend for Recorder {
+        fn will_save(&mut self) {
+            self.calls.push("save".into())
+        }
+
+        fn did_restore(&mut self) {
+            self.calls.push("restore".into())
+        }
+
+        fn did_concat(&mut self, matrix: &Matrix) {
+            self.calls.push(format!(
+                "concat {} {}",
+                matrix.translate_x(),
+                matrix.translate_y()
+            ))
+        }
+
+        fn on_clip_rect(&mut self, rect: &Rect, op: BackendClipOp, _anti_alias: bool) {
+            self.calls
+                .push(format!("clip_rect {} {:?}", rect.width(), op))
+        }
+
+        fn on_draw_rect(&mut self, rect: &Rect, paint: &Paint) {
+            self.calls
+                .push(format!("rect {} {:?}", rect.width(), paint.color()))
+        }
+
+        fn on_draw_path(&mut self, path: &Path, _paint: &Paint) {
+            self.calls.push(format!("path {}", path.count_points()))
+        }
+
+        fn on_draw_text_blob(&mut self, _blob: &TextBlob, origin: Point, _paint: &Paint) {
+            self.calls
+                .push(format!("text_blob {} {}", origin.x, origin.y))
+        }
+
+        fn on_draw_patch(
+            &mut self,
+            _cubics: &[Point; 12],
+            colors: Option<&[Color; 4]>,
+            _tex_coords: Option<&[Point; 4]>,
+            _mode: BlendMode,
+            _paint: &Paint,
+        ) {
+            self.calls.push(format!("patch {:?}", colors.map(|c| c[0])))
+        }
+
+        fn on_draw_image_nine(
+            &mut self,
+            _image: &Image,
+            center: &IRect,
+            _dst: &Rect,
+            _paint: Option<&Paint>,
+        ) {
+            self.calls.push(format!("image_nine {}", center.width()))
+        }
+
+        fn on_draw_atlas(
+            &mut self,
+            _atlas: &Image,
+            xforms: &[RSXform],
+            tex: &[Rect],
+            colors: Option<&[Color]>,
+            _mode: BlendMode,
+            _cull_rect: Option<&Rect>,
+            _paint: Option<&Paint>,
+        ) {
+            assert_eq!(xforms.len(), tex.len());
+            self.calls
+                .push(format!("atlas {} {}", xforms.len(), colors.is_some()))
+        }
+
+        fn on_draw_shadow_rec(&mut self, _path: &Path, rec: &ShadowRec) {
+            self.calls
+                .push(format!("shadow {} {:?}", rec.light_radius, rec.spot_color))
+        }
+    }
+
+    #[test]
+    fn canvas_calls_are_forwarded_to_the_backend() {
+        let mut recorder = Recorder::default();
+        {
+            let mut canvas = Canvas::from_backend((100, 100), &mut recorder);
+            let mut paint = Paint::default();
+            paint.set_color(Color::RED);
+
+            canvas.save();
+            canvas.translate((10, 20));
+            canvas.clip_rect(Rect::new(0.0, 0.0, 50.0, 50.0), None, None);
+            canvas.draw_rect(Rect::new(0.0, 0.0, 5.0, 5.0), &paint);
+            let mut path = Path::default();
+            path.move_to((0, 0)).line_to((10, 10));
+            canvas.draw_path(&path, &paint);
+            canvas.restore();
+            canvas.draw_str("Hi", (1, 2), &Font::default(), &paint);
+        }
+
+        assert_eq!(
+            recorder.calls,
+            vec![
+                "save".to_string(),
+                "concat 10 20".into(),
+                "clip_rect 50 Intersect".into(),
+                format!("rect 5 {:?}", Color::RED),
+                "path 2".into(),
+                "restore".into(),
+                "text_blob 1 2".into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn patches_nine_patches_atlases_and_shadows_are_forwarded() {
+        use crate::{BlendMode, Surface};
+
+        let image = Surface::new_raster_n32_premul((4, 4))
+            .unwrap()
+            .image_snapshot();
+        let mut recorder = Recorder::default();
+        {
+            let mut canvas = Canvas::from_backend((100, 100), &mut recorder);
+            let cubics = [Point::default(); 12];
+            let colors = [Color::RED; 4];
+            let tex_coords = [Point::default(); 4];
+            canvas.draw_patch(
+                &cubics,
+                &colors,
+                &tex_coords,
+                BlendMode::Modulate,
+                &Paint::default(),
+            );
+            canvas.draw_image_nine(
+                &image,
+                IRect::new(1, 1, 3, 3),
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                None,
+            );
+            canvas.draw_atlas(
+                &image,
+                &[RSXform::new(1.0, 0.0, (0, 0))],
+                &[Rect::new(0.0, 0.0, 2.0, 2.0)],
+                None,
+                BlendMode::Modulate,
+                None,
+                None,
+            );
+            let mut path = Path::default();
+            path.add_rect(Rect::new(0.0, 0.0, 10.0, 10.0), None);
+            canvas.draw_shadow(
+                &path,
+                (0.0, 0.0, 1.0),
+                (0.0, 0.0, 10.0),
+                5.0,
+                Color::BLACK,
+                Color::BLUE,
+                None,
+            );
+        }
+
+        assert_eq!(
+            recorder.calls,
+            vec![
+                format!("patch {:?}", Some(Color::RED)),
+                "image_nine 2".into(),
+                "atlas 1 false".into(),
+                format!("shadow 5 {:?}", Color::BLUE),
+            ]
+        );
+    }
+
+    #[test]
+    fn the_first_panic_of_the_backend_is_resumed_when_the_canvas_is_dropped() {
+        #[derive(Default)]
+        struct Panicking {
+            rects: usize,
+        }
+
+        impl CanvasBackend for Panicking {
+            fn on_draw_rect(&mut self, _rect: &Rect, _paint: &Paint) {
+                self.rects += 1;
+                panic!("on_draw_rect {}", self.rects)
+            }
+        }
+
+        let mut backend = Panicking::default();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut canvas = Canvas::from_backend((10, 10), &mut backend);
+            canvas.draw_rect(Rect::new(0.0, 0.0, 5.0, 5.0), &Paint::default());
+            canvas.draw_rect(Rect::new(0.0, 0.0, 5.0, 5.0), &Paint::default());
+        }));
+        let panic = result.unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some("on_draw_rect 1")
+        );
+        assert_eq!(backend.rects, 1);
+    }
+
+    #[test]
+    fn pictures_are_played_back_into_the_backend() {
+        let mut picture_recorder = PictureRecorder::new();
+        picture_recorder
+            .begin_recording(Rect::new(0.0, 0.0, 10.0, 10.0), None, None)
+            .draw_rect(Rect::new(0.0, 0.0, 3.0, 3.0), &Paint::default());
+        let picture = picture_recorder.finish_recording_as_picture(None).unwrap();
+
+        let mut recorder = Recorder::default();
+        {
+            let mut canvas = Canvas::from_backend((10, 10), &mut recorder);
+            picture.playback(&mut canvas);
+            canvas.draw_picture(&picture, None, None);
+        }
+        let rects = recorder
+            .calls
+            .iter()
+            .filter(|call| call.starts_with("rect 3"))
+            .count();
+        assert_eq!(rects, 2);
+    }
+}
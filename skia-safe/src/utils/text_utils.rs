@@ -1,5 +1,9 @@
+use crate::path::{self, Verb};
 use crate::prelude::*;
-use crate::{Canvas, Font, Paint, Path, Point, TextEncoding};
+use crate::{
+    scalar, Canvas, ContourMeasure, ContourMeasureIter, Font, GlyphId, Paint, Path, Point, RSXform,
+    TextBlob, TextBlobBuilder, TextEncoding, Vector,
+};
 use core::borrow::BorrowMut;
 use skia_bindings::{SkTextUtils, SkTextUtils_Align};
 
@@ -79,3 +83,379 @@ impl Path {
         get_path(text, p, font)
     }
 }
+
+/// Lays out `text` along `path` and returns a text blob in which every glyph is rotated to
+/// follow the path.
+///
+/// The text is aligned relative to `offset`, a distance along the path. Glyphs are placed
+/// at the position of their horizontal centers, glyphs whose center does not lie on the path
+/// are omitted. Returns `None` if no glyph lies on the path.
+pub fn text_blob_on_path(
+    text: impl AsRef<str>,
+    path: &Path,
+    offset: scalar,
+    font: &Font,
+    align: Align,
+) -> Option<TextBlob> {
+    let contours = Contours::new(path);
+    let mut glyphs = Vec::new();
+    let mut xforms = Vec::new();
+    for glyph in layout_glyphs(text.as_ref(), offset, font, align) {
+        let half_width = glyph.width / 2.0;
+        if let Some((p, tangent)) = contours.pos_tan(glyph.start + half_width) {
+            glyphs.push(glyph.id);
+            xforms.push(RSXform::new(tangent.x, tangent.y, p - tangent * half_width));
+        }
+    }
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    let mut builder = TextBlobBuilder::new();
+    {
+        let (run_glyphs, run_xforms) = builder.alloc_run_rsxform(font, glyphs.len());
+        run_glyphs.copy_from_slice(&glyphs);
+        run_xforms.copy_from_slice(&xforms);
+    }
+    builder.make()
+}
+
+/// Lays out `text` along `path` like `text_blob_on_path()`, but returns the outlines of the
+/// glyphs warped so that they are bent along the path.
+pub fn get_warped_path(
+    text: impl AsRef<str>,
+    path: &Path,
+    offset: scalar,
+    font: &Font,
+    align: Align,
+) -> Path {
+    let contours = Contours::new(path);
+    let glyphs: Vec<PlacedGlyph> = layout_glyphs(text.as_ref(), offset, font, align)
+        .into_iter()
+        .filter(|glyph| contours.contains(glyph.start + glyph.width / 2.0))
+        .collect();
+    let ids: Vec<GlyphId> = glyphs.iter().map(|glyph| glyph.id).collect();
+
+    let mut warped = Path::default();
+    let mut glyphs = glyphs.iter();
+    font.get_paths(&ids, |_, glyph_path, matrix| {
+        let glyph = glyphs.next().unwrap();
+        if let Some(glyph_path) = glyph_path {
+            let mut glyph_path = glyph_path.clone();
+            glyph_path.transform(matrix);
+            warp_path(&glyph_path, glyph.start, &contours, &mut warped);
+        }
+    });
+    warped
+}
+
+/// Draws `text` along `path`, see `text_blob_on_path()`. If `warp` is set, the glyph outlines
+/// are bent along the path, see `get_warped_path()`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_str_on_path(
+    mut canvas: impl AsMut<Canvas>,
+    text: impl AsRef<str>,
+    path: &Path,
+    offset: scalar,
+    font: &Font,
+    paint: &Paint,
+    align: Align,
+    warp: bool,
+) {
+    let canvas = canvas.as_mut();
+    if warp {
+        canvas.draw_path(&get_warped_path(text, path, offset, font, align), paint);
+    } else if let Some(blob) = text_blob_on_path(text, path, offset, font, align) {
+        canvas.draw_text_blob(&blob, Point::default(), paint);
+    }
+}
+
+impl Canvas {
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_str_on_path(
+        &mut self,
+        text: impl AsRef<str>,
+        path: &Path,
+        offset: scalar,
+        font: &Font,
+        paint: &Paint,
+        align: Align,
+        warp: bool,
+    ) -> &mut Self {
+        draw_str_on_path(
+            self.borrow_mut(),
+            text,
+            path,
+            offset,
+            font,
+            paint,
+            align,
+            warp,
+        );
+        self
+    }
+}
+
+struct PlacedGlyph {
+    id: GlyphId,
+    /// The distance along the path of the glyph's origin.
+    start: scalar,
+    width: scalar,
+}
+
+fn layout_glyphs(text: &str, offset: scalar, font: &Font, align: Align) -> Vec<PlacedGlyph> {
+    let ids = font.str_to_glyphs_vec(text);
+    let mut widths = vec![0.0; ids.len()];
+    font.get_widths(&ids, &mut widths);
+    let total_width: scalar = widths.iter().sum();
+    let mut start = offset
+        - match align {
+            Align::Left => 0.0,
+            Align::Center => total_width / 2.0,
+            Align::Right => total_width,
+        };
+    ids.into_iter()
+        .zip(widths)
+        .map(|(id, width)| {
+            let glyph = PlacedGlyph { id, start, width };
+            start += width;
+            glyph
+        })
+        .collect()
+}
+
+/// The contours of a path, measured as if they were connected.
+struct Contours {
+    // the distance at which each contour starts.
+    contours: Vec<(scalar, ContourMeasure)>,
+    length: scalar,
+}
+
+impl Contours {
+    fn new(path: &Path) -> Contours {
+        let mut contours = Vec::new();
+        let mut length = 0.0;
+        for contour in ContourMeasureIter::from_path(path, false, None) {
+            let contour_length = contour.length();
+            contours.push((length, contour));
+            length += contour_length;
+        }
+        Contours { contours, length }
+    }
+
+    fn contains(&self, distance: scalar) -> bool {
+        (0.0..=self.length).contains(&distance)
+    }
+
+    /// Returns the position and the unit tangent at `distance`, or `None` if the distance
+    /// is not on the path.
+    fn pos_tan(&self, distance: scalar) -> Option<(Point, Vector)> {
+        if !self.contains(distance) {
+            return None;
+        }
+        let (start, contour) = self
+            .contours
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= distance)?;
+        contour.pos_tan(distance - start)
+    }
+
+    /// Maps a point of a horizontal line of text onto the path, the point's x coordinate
+    /// is the distance along the path and its y coordinate the distance from the path.
+    fn map(&self, p: Point) -> Point {
+        // positions outside of the path are pinned to its ends.
+        let distance = p.x.max(0.0).min(self.length);
+        match self.pos_tan(distance) {
+            Some((position, tangent)) => {
+                let normal = Vector::new(-tangent.y, tangent.x);
+                position + normal * p.y
+            }
+            None => p,
+        }
+    }
+}
+
+/// Appends `glyph_path` bent along the path of `contours` to `warped`, `start` is the
+/// distance along the path of the glyph's origin.
+fn warp_path(glyph_path: &Path, start: scalar, contours: &Contours, warped: &mut Path) {
+    let map = |p: Point| contours.map(p + Vector::new(start, 0.0));
+    let mut iter = path::Iter::new(glyph_path, false);
+    loop {
+        let (verb, points) = iter.next(None, None);
+        match verb {
+            Verb::Move => {
+                warped.move_to(map(points[0]));
+            }
+            // lines are converted to quads so that they can bend.
+            Verb::Line => {
+                let mid = Point::new(
+                    (points[0].x + points[1].x) / 2.0,
+                    (points[0].y + points[1].y) / 2.0,
+                );
+                warped.quad_to(map(mid), map(points[1]));
+            }
+            Verb::Quad => {
+                warped.quad_to(map(points[1]), map(points[2]));
+            }
+            Verb::Conic => {
+                warped.conic_to(map(points[1]), map(points[2]), iter.conic_weight());
+            }
+            Verb::Qubic => {
+                warped.cubic_to(map(points[1]), map(points[2]), map(points[3]));
+            }
+            Verb::Close => {
+                warped.close();
+            }
+            Verb::Done => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rect;
+
+    fn line(from: impl Into<Point>, to: impl Into<Point>) -> Path {
+        let mut path = Path::default();
+        path.move_to(from).line_to(to);
+        path
+    }
+
+    fn assert_rect_eq(a: Rect, b: Rect) {
+        let eq = |a: scalar, b: scalar| (a - b).abs() < 0.01;
+        assert!(
+            eq(a.left, b.left)
+                && eq(a.top, b.top)
+                && eq(a.right, b.right)
+                && eq(a.bottom, b.bottom),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn warped_text_on_a_horizontal_line_is_not_bent() {
+        let font = Font::default();
+        let path = line((10, 50), (300, 50));
+        let warped = get_warped_path("Hi", &path, 0.0, &font, Align::Left);
+        let expected = get_path("Hi", (10, 50), &font);
+        assert_rect_eq(
+            warped.compute_tight_bounds(),
+            expected.compute_tight_bounds(),
+        );
+    }
+
+    #[test]
+    fn centered_text_on_a_line() {
+        let font = Font::default();
+        let path = line((0, 50), (200, 50));
+        let width = font.measure_str("Hi", None).0;
+        let warped = get_warped_path("Hi", &path, 100.0, &font, Align::Center);
+        let expected = get_path("Hi", (100.0 - width / 2.0, 50.0), &font);
+        assert_rect_eq(
+            warped.compute_tight_bounds(),
+            expected.compute_tight_bounds(),
+        );
+    }
+
+    #[test]
+    fn glyphs_beyond_the_path_are_omitted() {
+        let font = Font::default();
+        let path = line((0, 50), (1, 50));
+        assert!(text_blob_on_path("Hello", &path, 0.0, &font, Align::Left).is_none());
+        assert!(get_warped_path("Hello", &path, 0.0, &font, Align::Left).is_empty());
+        assert!(text_blob_on_path("", &path, 0.0, &font, Align::Left).is_none());
+    }
+
+    const SIZE: i32 = 200;
+
+    fn draw(f: impl FnOnce(&mut Canvas)) -> Vec<u32> {
+        let mut pixels = vec![0u32; (SIZE * SIZE) as usize];
+        {
+            let mut canvas =
+                Canvas::from_raster_direct_n32((SIZE, SIZE), pixels.as_mut_slice(), None).unwrap();
+            f(&mut canvas);
+        }
+        pixels
+    }
+
+    /// The left, top, right and bottom edges of the pixels that were drawn.
+    fn ink_bounds(pixels: &[u32]) -> (i32, i32, i32, i32) {
+        let mut bounds = (SIZE, SIZE, 0, 0);
+        for (i, _) in pixels.iter().enumerate().filter(|(_, p)| **p != 0) {
+            let (x, y) = (i as i32 % SIZE, i as i32 / SIZE);
+            bounds = (
+                bounds.0.min(x),
+                bounds.1.min(y),
+                bounds.2.max(x + 1),
+                bounds.3.max(y + 1),
+            );
+        }
+        assert!(bounds.0 < bounds.2, "nothing was drawn");
+        bounds
+    }
+
+    fn assert_bounds_near(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)) {
+        let near = |a: i32, b: i32| (a - b).abs() <= 2;
+        assert!(
+            near(a.0, b.0) && near(a.1, b.1) && near(a.2, b.2) && near(a.3, b.3),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn text_on_a_horizontal_line_is_placed_like_draw_str() {
+        let font = Font::default();
+        let paint = Paint::default();
+        let path = line((0, 50), (SIZE, 50));
+        for &align in &[Align::Left, Align::Center, Align::Right] {
+            let on_path = draw(|canvas| {
+                canvas.draw_str_on_path("Hello", &path, 100.0, &font, &paint, align, false);
+            });
+            let expected = draw(|canvas| {
+                canvas.draw_str_align("Hello", (100, 50), &font, &paint, align);
+            });
+            assert_bounds_near(ink_bounds(&on_path), ink_bounds(&expected));
+        }
+    }
+
+    #[test]
+    fn glyphs_on_a_vertical_line_are_rotated_clockwise() {
+        let font = Font::default();
+        let paint = Paint::default();
+        let on_path = draw(|canvas| {
+            let path = line((50, 0), (50, SIZE));
+            canvas.draw_str_on_path("Hello", &path, 20.0, &font, &paint, Align::Left, false);
+        });
+        let horizontal = draw(|canvas| {
+            canvas.draw_str_align("Hello", (20, 50), &font, &paint, Align::Left);
+        });
+        // rotating by 90 degrees around (50, 50) maps (x, y) to (100 - y, x), so the tops of
+        // the glyphs are on the right of the line and the text runs downwards.
+        let (left, top, right, bottom) = ink_bounds(&horizontal);
+        assert_bounds_near(ink_bounds(&on_path), (100 - bottom, left, 100 - top, right));
+    }
+
+    #[test]
+    fn draw_text_along_a_circle() {
+        let font = Font::default();
+        let mut path = Path::default();
+        path.add_circle((32, 32), 20.0, None);
+        let paint = Paint::default();
+
+        for warp in &[false, true] {
+            let mut pixels = [0u32; 64 * 64];
+            {
+                let mut canvas =
+                    Canvas::from_raster_direct_n32((64, 64), pixels.as_mut(), None).unwrap();
+                canvas.draw_str_on_path("Hello", &path, 10.0, &font, &paint, Align::Left, *warp);
+            }
+            assert!(pixels.iter().any(|&p| p != 0));
+        }
+    }
+}